@@ -22,11 +22,17 @@ import {
     STAKING_PROGRAM_ID,
     GLOBAL_AUTHORITY_SEED,
     GlobalPool,
-    USER_POOL_SIZE,
     UserPool,
     DECIMALS,
     FarmData,
-    FARM_POOL_SIZE,
+    FARM_SEED,
+    ADMIN_ROLES_SEED,
+    DENYLIST_SEED,
+    USER_POOL_SEED,
+    RECEIPT_COUNTER_SEED,
+    LEGACY_USER_POOL_SEED,
+    LEGACY_USER_POOL_SIZE,
+    LegacyUserPool,
 } from './types';
 import {
     getAssociatedTokenAccount,
@@ -64,10 +70,9 @@ const main = async () => {
 
     // await initUserPool(payer.publicKey);
 
-    // await createFarm(payer.publicKey, [100, 100, 500, 0], [0.1, 0.2, 0.3, 0.4], 10);
-    // let farmKey = await anchor.web3.PublicKey.createWithSeed(
-    //     payer.publicKey,
-    //     "3",
+    // await createFarm(payer.publicKey, [100, 100, 500, 0], [0.1, 0.2, 0.3, 0.4], 10, rewardMint);
+    // const [farmKey] = await PublicKey.findProgramAddress(
+    //     [Buffer.from(FARM_SEED), new anchor.BN(3).toArrayLike(Buffer, "le", 8)],
     //     STAKING_PROGRAM_ID,
     // );
     // let farm  = await getFarmState(farmKey, program);
@@ -138,30 +143,16 @@ const main = async () => {
 export const initUserPool = async (
     userAddress: PublicKey
 ) => {
-    let userPoolKey = await anchor.web3.PublicKey.createWithSeed(
-        userAddress,
-        "user-pool",
-        STAKING_PROGRAM_ID,
-    );
-    console.log(USER_POOL_SIZE);
-    let ix = SystemProgram.createAccountWithSeed({
-        fromPubkey: userAddress,
-        basePubkey: userAddress,
-        seed: "user-pool",
-        newAccountPubkey: userPoolKey,
-        lamports: await solConnection.getMinimumBalanceForRentExemption(USER_POOL_SIZE),
-        space: USER_POOL_SIZE,
-        programId: STAKING_PROGRAM_ID,
-    });
+    const userPoolKey = await getUserPoolKey(userAddress);
 
     let tx = new Transaction();
     console.log('==>initializing user PDA', userPoolKey.toBase58());
-    tx.add(ix);
     tx.add(program.instruction.initializeUserPool(
         {
             accounts: {
                 userPool: userPoolKey,
-                owner: userAddress
+                owner: userAddress,
+                systemProgram: SystemProgram.programId,
             },
             instructions: [],
             signers: []
@@ -180,58 +171,60 @@ export const initUserPool = async (
 
 /**
  * Create the Farm function
- * @param userAddress The userAddress who create farm - admin address
- * @param duration The tier_duration[4]
- * @param rate The tier_rate[4]
- * @param maxCount The max stake count for the Farm Pool
+ * @param userAddress The userAddress who create farm - admin address or farm manager
+ * @param duration The tier_duration, one per tier up to 8
+ * @param rate The tier_rate, one per tier
+ * @param maxCount The max stake count of one owner in the Farm Pool
+ * @param rewardMint The mint of the farm's reward
+ * @param minLockDuration Seconds a staked NFT can't be withdrawn
+ * @param cooldownDuration Seconds between requesting an unstake and withdrawing, 0 without cooldown
  */
  export const createFarm = async (
     userAddress: PublicKey,
     duration: number[],
     rate: number[],
-    maxCount: number
+    maxCount: number,
+    rewardMint: PublicKey,
+    minLockDuration: number = 0,
+    cooldownDuration: number = 0,
 ) => {
     const [globalAuthority, bump] = await PublicKey.findProgramAddress(
         [Buffer.from(GLOBAL_AUTHORITY_SEED)],
         STAKING_PROGRAM_ID,
     );
+    const [adminRoles] = await PublicKey.findProgramAddress(
+        [Buffer.from(ADMIN_ROLES_SEED)],
+        STAKING_PROGRAM_ID,
+    );
     
     let info = await getGlobalState(program);
-    let count = info.farmCount.toNumber() + 1;
-    console.log("Farm count is : ", count);
+    let count = new anchor.BN(info.farmCount.toNumber() + 1);
+    console.log("Farm count is : ", count.toNumber());
 
-    let tduration: anchor.BN[] = [];
-    let trate: anchor.BN[]= [];
-    for(let i = 0; i < 4; i++) { 
-        tduration[i] = new anchor.BN(duration[i]);
-        trate[i] = new anchor.BN(rate[i] * DECIMALS);
-    }
+    let tduration = duration.map((value) => new anchor.BN(value));
+    let trate = rate.map((value) => new anchor.BN(value * DECIMALS));
 
-    let farmKey = await anchor.web3.PublicKey.createWithSeed(
-        userAddress,
-        count.toString(),
+    const [farmKey] = await PublicKey.findProgramAddress(
+        [Buffer.from(FARM_SEED), count.toArrayLike(Buffer, "le", 8)],
         STAKING_PROGRAM_ID,
     );
-    console.log(FARM_POOL_SIZE);
-    let ix = SystemProgram.createAccountWithSeed({
-        fromPubkey: userAddress,
-        basePubkey: userAddress,
-        seed: count.toString(),
-        newAccountPubkey: farmKey,
-        lamports: await solConnection.getMinimumBalanceForRentExemption(FARM_POOL_SIZE),
-        space: FARM_POOL_SIZE,
-        programId: STAKING_PROGRAM_ID,
-    });
 
     let tx = new Transaction();
     console.log('==>Creating Farm ', farmKey.toBase58());
-    tx.add(ix);
     tx.add(program.instruction.createFarm(
-        tduration, trate, new anchor.BN(maxCount), new anchor.BN(count), {
+        tduration,
+        trate,
+        new anchor.BN(maxCount),
+        new anchor.BN(minLockDuration),
+        new anchor.BN(cooldownDuration),
+        count, {
             accounts: {
                 admin: userAddress,
                 globalAuthority,
-                farmPool: farmKey
+                adminRoles,
+                farmPool: farmKey,
+                rewardMint,
+                systemProgram: SystemProgram.programId,
             },
             instructions: [],
             signers: []
@@ -457,7 +450,7 @@ export const calculateReward = async (
     if (now === null) return 0;
     let reward = 0;
     
-    for (let i = 0; i < userPool.stakedCount.toNumber(); i++) {
+    for (let i = 0; i < userPool.staking.length; i++) {
         if (userPool.staking[i].mint.toBase58() === nftMint.toBase58()) {
             let duration = now - userPool.staking[i].claimedTime.toNumber();
             for (let j = 0;  j < 3; j++){
@@ -492,15 +485,11 @@ export const stakeNFT = async (
     farmPool: PublicKey,
     mint: PublicKey,
 ) => {
-    let userPoolKey = await anchor.web3.PublicKey.createWithSeed(
-        payer.publicKey,
-        "user-pool",
-        STAKING_PROGRAM_ID,
-    );
+    const userPoolKey = await getUserPoolKey(userAddress);
 
     let poolAccount = await solConnection.getAccountInfo(userPoolKey);
     if (poolAccount === null || poolAccount.data === null) {
-        await initUserPool(payer.publicKey);
+        await initUserPool(userAddress);
     }
 
     const [globalAuthority, bump] = await PublicKey.findProgramAddress(
@@ -546,9 +535,19 @@ export const stakeNFT = async (
         [first_creator.toBuffer(), farmPool.toBuffer()],
         STAKING_PROGRAM_ID,
     );
+    // Required even when the mint isn't denied, the program rejects stakes without it
+    const [denyProof] = await PublicKey.findProgramAddress(
+        [mint.toBuffer(), farmPool.toBuffer(), Buffer.from(DENYLIST_SEED)],
+        STAKING_PROGRAM_ID,
+    );
 
 
     let remainingAccounts = [
+        {
+            pubkey: denyProof,
+            isSigner: false,
+            isWritable: false,
+        },
         {
             pubkey: whitelistMintProof,
             isSigner: false,
@@ -567,11 +566,16 @@ export const stakeNFT = async (
     if (instructions.length > 0) instructions.map((ix) => tx.add(ix));
     console.log('==>Staking ...', mint.toBase58());
 
+    const [receiptCounter] = await PublicKey.findProgramAddress(
+        [Buffer.from(RECEIPT_COUNTER_SEED), farmPool.toBuffer(), userAddress.toBuffer()],
+        STAKING_PROGRAM_ID,
+    );
     tx.add(program.instruction.stakeNftToPool(
-        bump, {
+        bump, null, null, {
         accounts: {
             owner: userAddress,
             userPool: userPoolKey,
+            receiptCounter,
             farmPool,
             globalAuthority,
             userNftTokenAccount: userTokenAccount,
@@ -580,6 +584,7 @@ export const stakeNFT = async (
             mintMetadata: metadata,
             tokenProgram: TOKEN_PROGRAM_ID,
             tokenMetadataProgram: METAPLEX,
+            systemProgram: SystemProgram.programId,
         },
         remainingAccounts,
         instructions: [],
@@ -602,10 +607,11 @@ export const getUserPoolInfo = async (
     const userInfo: UserPool = await getUserPoolState(userAddress, program);
     return {
         owner: userInfo.owner.toBase58(),
-        stakedCount: userInfo.stakedCount.toNumber(),
+        stakedCount: userInfo.staking.length,
         staking: userInfo.staking.map((info) => {
             return {
                 mint: info.mint.toBase58(),
+                farm: info.farm.toBase58(),
                 farmNumber: info.farmNumber.toNumber(),
                 stakedTime: info.stakedTime.toNumber(),
                 claimedTime: info.claimedTime.toNumber(),
@@ -642,15 +648,12 @@ export const getUserPoolInfo = async (
         STAKING_PROGRAM_ID
     );
 
-    let userPoolKey = await anchor.web3.PublicKey.createWithSeed(
-        userAddress,
-        "user-pool",
-        STAKING_PROGRAM_ID,
-    );
+    const userPoolKey = await getUserPoolKey(userAddress);
 
     let tx = new Transaction();
     console.log('==> Claiming Reward ... ', mint.toBase58());
     if (ret.instructions.length > 0) ret.instructions.map((ix) => tx.add(ix));
+    const farmData = await getFarmState(farmPool, program);
     tx.add(program.instruction.claimReward(
         bump, {
         accounts: {
@@ -660,6 +663,7 @@ export const getUserPoolInfo = async (
             globalAuthority,
             userRewardAccount: ret.destinationAccounts[0],
             rewardMint,
+            rewardVault: farmData.rewardVault,
             nftMint: mint,
             tokenProgram: TOKEN_PROGRAM_ID,
         },
@@ -705,12 +709,9 @@ export const withdrawNft = async (
     );
     let destNftTokenAccount = await getAssociatedTokenAccount(globalAuthority, mint);
 
-    let userPoolKey = await anchor.web3.PublicKey.createWithSeed(
-        userAddress,
-        "user-pool",
-        STAKING_PROGRAM_ID,
-    );
+    const userPoolKey = await getUserPoolKey(userAddress);
 
+    const farmData = await getFarmState(farmPool, program);
     let tx = new Transaction();
     console.log('==> Withdrawing ... ', mint.toBase58());
     if (ret.instructions.length > 0) ret.instructions.map((ix) => tx.add(ix));
@@ -725,6 +726,65 @@ export const withdrawNft = async (
             destNftTokenAccount,
             userRewardAccount: ret.destinationAccounts[1],
             rewardMint,
+            rewardVault: farmData.rewardVault,
+            penaltyTreasury: farmData.penaltyTreasury,
+            nftMint: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+        },
+        instructions: [],
+        signers: [],
+    }));
+    const { blockhash } = await solConnection.getRecentBlockhash('confirmed');
+    tx.feePayer = payer.publicKey;
+    tx.recentBlockhash = blockhash;
+    payer.signTransaction(tx);
+    let txId = await solConnection.sendTransaction(tx, [(payer as NodeWallet).payer]);
+    await solConnection.confirmTransaction(txId, "confirmed");
+    console.log("Your transaction signature", txId);
+}
+
+
+/**
+ * Withdraw an NFT staked in the first version's user pool, which pays no reward
+ * @param userAddress The caller address
+ * @param mint The NFT mint address
+ */
+export const withdrawLegacyNft = async (
+    userAddress: PublicKey,
+    mint: PublicKey,
+) => {
+    let ret = await getATokenAccountsNeedCreate(
+        solConnection,
+        userAddress,
+        userAddress,
+        [mint]
+    );
+    let userTokenAccount = ret.destinationAccounts[0];
+    console.log("User NFT = ", mint.toBase58(), userTokenAccount.toBase58());
+
+    const [globalAuthority, bump] = await PublicKey.findProgramAddress(
+        [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+        STAKING_PROGRAM_ID
+    );
+    let destNftTokenAccount = await getAssociatedTokenAccount(globalAuthority, mint);
+
+    let legacyUserPool = await anchor.web3.PublicKey.createWithSeed(
+        userAddress,
+        LEGACY_USER_POOL_SEED,
+        STAKING_PROGRAM_ID,
+    );
+
+    let tx = new Transaction();
+    console.log('==> Withdrawing Legacy ... ', mint.toBase58());
+    if (ret.instructions.length > 0) ret.instructions.map((ix) => tx.add(ix));
+    tx.add(program.instruction.unstakeLegacyNft(
+        bump, {
+        accounts: {
+            owner: userAddress,
+            legacyUserPool,
+            globalAuthority,
+            userNftTokenAccount: userTokenAccount,
+            destNftTokenAccount,
             nftMint: mint,
             tokenProgram: TOKEN_PROGRAM_ID,
         },
@@ -867,15 +927,21 @@ export const getGlobalState = async (
     }
 }
 
+export const getUserPoolKey = async (
+    userAddress: PublicKey,
+): Promise<PublicKey> => {
+    const [userPoolKey] = await PublicKey.findProgramAddress(
+        [Buffer.from(USER_POOL_SEED), userAddress.toBuffer()],
+        STAKING_PROGRAM_ID,
+    );
+    return userPoolKey;
+}
+
 export const getUserPoolState = async (
     userAddress: PublicKey,
     program: anchor.Program,
 ): Promise<UserPool | null> => {
-    let userPoolKey = await anchor.web3.PublicKey.createWithSeed(
-        userAddress,
-        "user-pool",
-        STAKING_PROGRAM_ID,
-    );
+    const userPoolKey = await getUserPoolKey(userAddress);
     try {
         let userPoolState = await program.account.userPool.fetch(userPoolKey);
         return userPoolState as unknown as UserPool;
@@ -906,12 +972,16 @@ export const getAllStakedNFTs = async (connection: Connection, rpcUrl: string |
         solConnection = new anchor.web3.Connection(rpcUrl, "confirmed");
     }
 
+    // User pools grow with their stakes, so they're found by discriminator rather than size
     let poolAccounts = await solConnection.getProgramAccounts(
         STAKING_PROGRAM_ID,
         {
             filters: [
                 {
-                    dataSize: USER_POOL_SIZE,
+                    memcmp: {
+                        offset: 0,
+                        bytes: anchor.utils.bytes.bs58.encode(anchor.BorshAccountsCoder.accountDiscriminator("UserPool")),
+                    },
                 },
             ]
         }
@@ -921,6 +991,63 @@ export const getAllStakedNFTs = async (connection: Connection, rpcUrl: string |
 
     let result: UserPool[] = [];
 
+    try {
+        for (let idx = 0; idx < poolAccounts.length; idx++) {
+            let data = poolAccounts[idx].account.data;
+            // Legacy pools share the discriminator, getAllLegacyStakedNFTs reads them
+            if (data.length === LEGACY_USER_POOL_SIZE) continue;
+            result.push(program.coder.accounts.decode("UserPool", data) as UserPool);
+        }
+    } catch (e) {
+        console.log(e);
+        return {};
+    }
+
+    return {
+        count: result.length,
+        data: result.map((info: UserPool) => {
+            return {
+                owner: info.owner.toBase58(),
+                stakedCount: info.staking.length,
+                staking: info.staking.map((info) => {
+                    return {
+                        mint: info.mint.toBase58(),
+                        farm: info.farm.toBase58(),
+                        farmNumber: info.farmNumber.toNumber(),
+                        stakedTime: info.stakedTime.toNumber(),
+                        claimedTime: info.claimedTime.toNumber(),
+                    }
+                }),
+            }
+        })
+    }
+};
+
+/**
+ * NFTs still staked in the first version's user pools, which withdrawLegacyNft returns
+ */
+export const getAllLegacyStakedNFTs = async (connection: Connection, rpcUrl: string | undefined) => {
+    let solConnection = connection;
+
+    if (rpcUrl) {
+        solConnection = new anchor.web3.Connection(rpcUrl, "confirmed");
+    }
+
+    let poolAccounts = await solConnection.getProgramAccounts(
+        STAKING_PROGRAM_ID,
+        {
+            filters: [
+                {
+                    dataSize: LEGACY_USER_POOL_SIZE,
+                },
+            ]
+        }
+    );
+
+    console.log(`Encounter ${poolAccounts.length} Legacy NFT Data Accounts`);
+
+    let result: LegacyUserPool[] = [];
+
     try {
         for (let idx = 0; idx < poolAccounts.length; idx++) {
             let data = poolAccounts[idx].account.data;
@@ -961,7 +1088,7 @@ export const getAllStakedNFTs = async (connection: Connection, rpcUrl: string |
 
     return {
         count: result.length,
-        data: result.map((info: UserPool) => {
+        data: result.map((info: LegacyUserPool) => {
             return {
                 owner: info.owner.toBase58(),
                 stakedCount: info.stakedCount.toNumber(),
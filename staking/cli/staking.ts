@@ -43,8 +43,192 @@ export type Staking = {
         }
       ]
     },
+    {
+      "name": "proposeAdmin",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminProposal",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "newAdmin",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "cancelAdminProposal",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminProposal",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "acceptAdmin",
+      "accounts": [
+        {
+          "name": "newAdmin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "adminProposal",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "initAdminRoles",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminRoles",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "grantRole",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminRoles",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "operator",
+          "type": "publicKey"
+        },
+        {
+          "name": "role",
+          "type": {
+            "defined": "Role"
+          }
+        }
+      ]
+    },
+    {
+      "name": "revokeRole",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminRoles",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "operator",
+          "type": "publicKey"
+        },
+        {
+          "name": "role",
+          "type": {
+            "defined": "Role"
+          }
+        }
+      ]
+    },
     {
       "name": "initializeUserPool",
+      "accounts": [
+        {
+          "name": "userPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "closeUserPool",
       "accounts": [
         {
           "name": "userPool",
@@ -72,10 +256,25 @@ export type Staking = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "adminRoles",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "farmPool",
           "isMut": true,
           "isSigner": false
+        },
+        {
+          "name": "rewardMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
@@ -95,6 +294,14 @@ export type Staking = {
           "name": "maxStakeCount",
           "type": "u64"
         },
+        {
+          "name": "minLockDuration",
+          "type": "i64"
+        },
+        {
+          "name": "cooldownDuration",
+          "type": "i64"
+        },
         {
           "name": "count",
           "type": "u64"
@@ -114,6 +321,11 @@ export type Staking = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "adminRoles",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "farmPool",
           "isMut": true,
@@ -136,6 +348,14 @@ export type Staking = {
         {
           "name": "maxStakeCount",
           "type": "u64"
+        },
+        {
+          "name": "minLockDuration",
+          "type": "i64"
+        },
+        {
+          "name": "cooldownDuration",
+          "type": "i64"
         }
       ]
     },
@@ -152,6 +372,11 @@ export type Staking = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "adminRoles",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "farmPool",
           "isMut": true,
@@ -180,8 +405,14 @@ export type Staking = {
       ],
       "args": [
         {
-          "name": "isCollection",
-          "type": "bool"
+          "name": "kind",
+          "type": {
+            "defined": "WhitelistKind"
+          }
+        },
+        {
+          "name": "multiplierBps",
+          "type": "u64"
         }
       ]
     },
@@ -198,6 +429,11 @@ export type Staking = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "adminRoles",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "farmPool",
           "isMut": true,
@@ -229,6 +465,11 @@ export type Staking = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "adminRoles",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "farmPool",
           "isMut": true,
@@ -243,68 +484,79 @@ export type Staking = {
       ]
     },
     {
-      "name": "stakeNftToPool",
+      "name": "setMerkleRoot",
       "accounts": [
         {
-          "name": "owner",
+          "name": "admin",
           "isMut": true,
           "isSigner": true
         },
         {
-          "name": "userPool",
-          "isMut": true,
+          "name": "globalAuthority",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "farmPool",
-          "isMut": true,
+          "name": "adminRoles",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "globalAuthority",
+          "name": "farmPool",
           "isMut": true,
           "isSigner": false
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "userNftTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
+          "name": "merkleRoot",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "setRarityRoot",
+      "accounts": [
         {
-          "name": "destNftTokenAccount",
+          "name": "admin",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "nftMint",
+          "name": "globalAuthority",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "mintMetadata",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
+          "name": "adminRoles",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenMetadataProgram",
-          "isMut": false,
+          "name": "farmPool",
+          "isMut": true,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "globalBump",
-          "type": "u8"
+          "name": "rarityRoot",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
         }
       ]
     },
     {
-      "name": "claimReward",
+      "name": "stakeNftToPool",
       "accounts": [
         {
           "name": "owner",
@@ -317,8 +569,13 @@ export type Staking = {
           "isSigner": false
         },
         {
-          "name": "farmPool",
-          "isMut": true,
+          "name": "receiptCounter",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "farmPool",
+          "isMut": true,
           "isSigner": false
         },
         {
@@ -327,12 +584,12 @@ export type Staking = {
           "isSigner": false
         },
         {
-          "name": "userRewardAccount",
+          "name": "userNftTokenAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "rewardMint",
+          "name": "destNftTokenAccount",
           "isMut": true,
           "isSigner": false
         },
@@ -341,16 +598,52 @@ export type Staking = {
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "mintMetadata",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
+        },
+        {
+          "name": "tokenMetadataProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
         {
           "name": "globalBump",
           "type": "u8"
+        },
+        {
+          "name": "merkleProof",
+          "type": {
+            "option": {
+              "vec": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
+            }
+          }
+        },
+        {
+          "name": "rarity",
+          "type": {
+            "option": {
+              "defined": "RarityProof"
+            }
+          }
         }
       ]
     },
@@ -397,6 +690,16 @@ export type Staking = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "rewardVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "penaltyTreasury",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "nftMint",
           "isMut": false,
@@ -416,28 +719,135 @@ export type Staking = {
       ]
     },
     {
-      "name": "transferMintAuthority",
+      "name": "unstakeLegacyNft",
       "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "legacyUserPool",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "globalAuthority",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "userNftTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destNftTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "nftMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "globalBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "beginUnstake",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "userPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "farmPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "nftMint",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "finalizeUnstake",
+      "accounts": [
+        {
+          "name": "owner",
           "isMut": true,
           "isSigner": true
         },
+        {
+          "name": "userPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "farmPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userNftTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destNftTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userRewardAccount",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "rewardMint",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "newAuthority",
+          "name": "rewardVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "penaltyTreasury",
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "nftMint",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "tokenProgram",
           "isMut": false,
@@ -452,28 +862,89 @@ export type Staking = {
       ]
     },
     {
-      "name": "mintToAccount",
+      "name": "claimReward",
       "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "userPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "farmPool",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "globalAuthority",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "userRewardAccount",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
           "name": "rewardMint",
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "rewardVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "nftMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "globalBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "claimAll",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "userPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "userRewardAccount",
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "rewardMint",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "tokenProgram",
           "isMut": false,
@@ -486,447 +957,214 @@ export type Staking = {
           "type": "u8"
         },
         {
-          "name": "amount",
-          "type": "u64"
+          "name": "farmNumber",
+          "type": {
+            "option": "u64"
+          }
         }
       ]
-    }
-  ],
-  "accounts": [
-    {
-      "name": "globalPool",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "superAdmin",
-            "type": "publicKey"
-          },
-          {
-            "name": "rewardToken",
-            "type": "publicKey"
-          },
-          {
-            "name": "totalStakedCount",
-            "type": "u64"
-          },
-          {
-            "name": "farmCount",
-            "type": "u64"
-          }
-        ]
-      }
     },
     {
-      "name": "whitelistProof",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "whitelistAddress",
-            "type": "publicKey"
-          },
-          {
-            "name": "isCollection",
-            "type": "bool"
-          }
-        ]
-      }
-    },
-    {
-      "name": "farmData",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "farmNumber",
-            "type": "u64"
-          },
-          {
-            "name": "stakedCount",
-            "type": "u64"
-          },
-          {
-            "name": "maxStakeCount",
-            "type": "u64"
-          },
-          {
-            "name": "tierDuration",
-            "type": {
-              "array": [
-                "i64",
-                4
-              ]
-            }
-          },
-          {
-            "name": "tierRate",
-            "type": {
-              "array": [
-                "u64",
-                4
-              ]
-            }
-          },
-          {
-            "name": "isStop",
-            "type": "bool"
-          }
-        ]
-      }
-    },
-    {
-      "name": "userPool",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "owner",
-            "type": "publicKey"
-          },
-          {
-            "name": "stakedCount",
-            "type": "u64"
-          },
-          {
-            "name": "staking",
-            "type": {
-              "array": [
-                {
-                  "defined": "StakedData"
-                },
-                100
-              ]
-            }
-          }
-        ]
-      }
-    }
-  ],
-  "types": [
-    {
-      "name": "StakedData",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "mint",
-            "type": "publicKey"
-          },
-          {
-            "name": "farmNumber",
-            "type": "u64"
-          },
-          {
-            "name": "stakedTime",
-            "type": "i64"
-          },
-          {
-            "name": "claimedTime",
-            "type": "i64"
-          }
-        ]
-      }
-    },
-    {
-      "name": "StakingError",
-      "type": {
-        "kind": "enum",
-        "variants": [
-          {
-            "name": "InvalidSuperOwner"
-          },
-          {
-            "name": "InvalidInput"
-          },
-          {
-            "name": "InvalidWhitelistAddress"
-          },
-          {
-            "name": "InvalidFarmCount"
-          },
-          {
-            "name": "InvalidGlobalPool"
-          },
-          {
-            "name": "InvalidUserPool"
-          },
-          {
-            "name": "PoolStopped"
-          },
-          {
-            "name": "ExceedMaxCount"
-          },
-          {
-            "name": "InvalidWithdrawTime"
-          },
-          {
-            "name": "InvalidNFTAddress"
-          },
-          {
-            "name": "InsufficientRewardVault"
-          },
-          {
-            "name": "InvaliedMetadata"
-          },
-          {
-            "name": "MetadataCreatorParseError"
-          },
-          {
-            "name": "UnkownOrNotAllowedNFTCollection"
-          }
-        ]
-      }
-    }
-  ]
-};
-
-export const IDL: Staking = {
-  "version": "0.1.0",
-  "name": "staking",
-  "instructions": [
-    {
-      "name": "initialize",
+      "name": "stakeNftToReceipt",
       "accounts": [
         {
-          "name": "admin",
+          "name": "owner",
           "isMut": true,
           "isSigner": true
         },
         {
-          "name": "globalAuthority",
+          "name": "stakeReceipt",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "rewardToken",
+          "name": "receiptCounter",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "userPool",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
-          "isMut": false,
+          "name": "farmPool",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "rent",
-          "isMut": false,
+          "name": "globalAuthority",
+          "isMut": true,
           "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "globalBump",
-          "type": "u8"
-        }
-      ]
-    },
-    {
-      "name": "initializeUserPool",
-      "accounts": [
+        },
         {
-          "name": "userPool",
+          "name": "userNftTokenAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "owner",
+          "name": "destNftTokenAccount",
           "isMut": true,
-          "isSigner": true
-        }
-      ],
-      "args": []
-    },
-    {
-      "name": "createFarm",
-      "accounts": [
+          "isSigner": false
+        },
         {
-          "name": "admin",
-          "isMut": true,
-          "isSigner": true
+          "name": "nftMint",
+          "isMut": false,
+          "isSigner": false
         },
         {
-          "name": "globalAuthority",
+          "name": "mintMetadata",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "farmPool",
-          "isMut": true,
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMetadataProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "tierDuration",
-          "type": {
-            "vec": "i64"
-          }
+          "name": "globalBump",
+          "type": "u8"
         },
         {
-          "name": "tierRate",
+          "name": "merkleProof",
           "type": {
-            "vec": "u64"
+            "option": {
+              "vec": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
+            }
           }
         },
         {
-          "name": "maxStakeCount",
-          "type": "u64"
-        },
-        {
-          "name": "count",
-          "type": "u64"
+          "name": "rarity",
+          "type": {
+            "option": {
+              "defined": "RarityProof"
+            }
+          }
         }
       ]
     },
     {
-      "name": "updateFarm",
+      "name": "beginReceiptUnstake",
       "accounts": [
         {
-          "name": "admin",
+          "name": "owner",
           "isMut": true,
           "isSigner": true
         },
         {
-          "name": "globalAuthority",
+          "name": "stakeReceipt",
           "isMut": true,
           "isSigner": false
         },
         {
           "name": "farmPool",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "newTierDuration",
-          "type": {
-            "vec": "i64"
-          }
-        },
-        {
-          "name": "newTierRate",
-          "type": {
-            "vec": "u64"
-          }
         },
         {
-          "name": "maxStakeCount",
-          "type": "u64"
+          "name": "nftMint",
+          "isMut": false,
+          "isSigner": false
         }
-      ]
+      ],
+      "args": []
     },
     {
-      "name": "addWhitelist",
+      "name": "unstakeNftFromReceipt",
       "accounts": [
         {
-          "name": "admin",
+          "name": "owner",
           "isMut": true,
           "isSigner": true
         },
         {
-          "name": "globalAuthority",
+          "name": "stakeReceipt",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "farmPool",
+          "name": "receiptCounter",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "whitelistAddress",
+          "name": "farmPool",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "whitelistProof",
+          "name": "globalAuthority",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
-          "isMut": false,
+          "name": "userNftTokenAccount",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "isCollection",
-          "type": "bool"
-        }
-      ]
-    },
-    {
-      "name": "removeWhitelist",
-      "accounts": [
-        {
-          "name": "admin",
+          "name": "destNftTokenAccount",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "globalAuthority",
+          "name": "userRewardAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "farmPool",
+          "name": "rewardMint",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "whitelistAddress",
+          "name": "rewardVault",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "whitelistProof",
+          "name": "penaltyTreasury",
           "isMut": true,
           "isSigner": false
-        }
-      ],
-      "args": []
-    },
-    {
-      "name": "setStop",
-      "accounts": [
-        {
-          "name": "admin",
-          "isMut": true,
-          "isSigner": true
         },
         {
-          "name": "globalAuthority",
-          "isMut": true,
+          "name": "nftMint",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "farmPool",
-          "isMut": true,
+          "name": "tokenProgram",
+          "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "isStop",
-          "type": "bool"
+          "name": "globalBump",
+          "type": "u8"
         }
       ]
     },
     {
-      "name": "stakeNftToPool",
+      "name": "claimReceiptReward",
       "accounts": [
         {
           "name": "owner",
@@ -934,13 +1172,13 @@ export const IDL: Staking = {
           "isSigner": true
         },
         {
-          "name": "userPool",
+          "name": "stakeReceipt",
           "isMut": true,
           "isSigner": false
         },
         {
           "name": "farmPool",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
@@ -949,32 +1187,27 @@ export const IDL: Staking = {
           "isSigner": false
         },
         {
-          "name": "userNftTokenAccount",
+          "name": "userRewardAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "destNftTokenAccount",
+          "name": "rewardMint",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "nftMint",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "mintMetadata",
+          "name": "rewardVault",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "nftMint",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenMetadataProgram",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         }
@@ -987,7 +1220,7 @@ export const IDL: Staking = {
       ]
     },
     {
-      "name": "claimReward",
+      "name": "migrateToReceipt",
       "accounts": [
         {
           "name": "owner",
@@ -1000,54 +1233,90 @@ export const IDL: Staking = {
           "isSigner": false
         },
         {
-          "name": "farmPool",
+          "name": "stakeReceipt",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "globalAuthority",
+          "name": "receiptCounter",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "userRewardAccount",
-          "isMut": true,
+          "name": "farmPool",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "rewardMint",
-          "isMut": true,
+          "name": "nftMint",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "nftMint",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setUnstakePenalty",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
         },
         {
-          "name": "tokenProgram",
+          "name": "globalAuthority",
           "isMut": false,
           "isSigner": false
+        },
+        {
+          "name": "adminRoles",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "farmPool",
+          "isMut": true,
+          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "globalBump",
-          "type": "u8"
+          "name": "penaltyDuration",
+          "type": "i64"
+        },
+        {
+          "name": "penaltyBps",
+          "type": "u64"
+        },
+        {
+          "name": "penaltyTreasury",
+          "type": {
+            "option": "publicKey"
+          }
         }
       ]
     },
     {
-      "name": "unstakeNftFromPool",
+      "name": "initRewardVault",
       "accounts": [
         {
-          "name": "owner",
+          "name": "admin",
           "isMut": true,
           "isSigner": true
         },
         {
-          "name": "userPool",
-          "isMut": true,
+          "name": "globalAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminRoles",
+          "isMut": false,
           "isSigner": false
         },
         {
@@ -1056,35 +1325,66 @@ export const IDL: Staking = {
           "isSigner": false
         },
         {
-          "name": "globalAuthority",
-          "isMut": true,
+          "name": "rewardMint",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "userNftTokenAccount",
+          "name": "rewardVault",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "destNftTokenAccount",
-          "isMut": true,
+          "name": "tokenProgram",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "userRewardAccount",
-          "isMut": true,
+          "name": "systemProgram",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "rewardMint",
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "fundRewardVault",
+      "accounts": [
+        {
+          "name": "admin",
           "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "nftMint",
+          "name": "adminRoles",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "farmPool",
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "adminRewardAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardVault",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "tokenProgram",
           "isMut": false,
@@ -1093,8 +1393,8 @@ export const IDL: Staking = {
       ],
       "args": [
         {
-          "name": "globalBump",
-          "type": "u8"
+          "name": "amount",
+          "type": "u64"
         }
       ]
     },
@@ -1142,6 +1442,11 @@ export const IDL: Staking = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "adminRoles",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "admin",
           "isMut": true,
@@ -1200,6 +1505,42 @@ export const IDL: Staking = {
         ]
       }
     },
+    {
+      "name": "adminProposal",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "proposedAdmin",
+            "type": "publicKey"
+          },
+          {
+            "name": "proposedTime",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "adminRoles",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "grants",
+            "type": {
+              "vec": {
+                "defined": "RoleGrant"
+              }
+            }
+          }
+        ]
+      }
+    },
     {
       "name": "whitelistProof",
       "type": {
@@ -1210,8 +1551,14 @@ export const IDL: Staking = {
             "type": "publicKey"
           },
           {
-            "name": "isCollection",
-            "type": "bool"
+            "name": "kind",
+            "type": {
+              "defined": "WhitelistKind"
+            }
+          },
+          {
+            "name": "multiplierBps",
+            "type": "u64"
           }
         ]
       }
@@ -1233,12 +1580,16 @@ export const IDL: Staking = {
             "name": "maxStakeCount",
             "type": "u64"
           },
+          {
+            "name": "tierCount",
+            "type": "u64"
+          },
           {
             "name": "tierDuration",
             "type": {
               "array": [
                 "i64",
-                4
+                8
               ]
             }
           },
@@ -1247,119 +1598,4098 @@ export const IDL: Staking = {
             "type": {
               "array": [
                 "u64",
-                4
+                8
               ]
             }
           },
           {
             "name": "isStop",
             "type": "bool"
-          }
-        ]
-      }
-    },
-    {
-      "name": "userPool",
-      "type": {
-        "kind": "struct",
-        "fields": [
+          },
           {
-            "name": "owner",
+            "name": "rewardVault",
             "type": "publicKey"
           },
           {
-            "name": "stakedCount",
-            "type": "u64"
+            "name": "rewardMint",
+            "type": "publicKey"
           },
           {
-            "name": "staking",
-            "type": {
-              "array": [
-                {
-                  "defined": "StakedData"
-                },
-                100
-              ]
-            }
-          }
-        ]
-      }
-    }
-  ],
-  "types": [
-    {
-      "name": "StakedData",
-      "type": {
-        "kind": "struct",
-        "fields": [
+            "name": "minLockDuration",
+            "type": "i64"
+          },
           {
-            "name": "mint",
-            "type": "publicKey"
+            "name": "cooldownDuration",
+            "type": "i64"
           },
           {
-            "name": "farmNumber",
+            "name": "penaltyDuration",
+            "type": "i64"
+          },
+          {
+            "name": "penaltyBps",
             "type": "u64"
           },
           {
-            "name": "stakedTime",
-            "type": "i64"
+            "name": "penaltyTreasury",
+            "type": "publicKey"
           },
           {
-            "name": "claimedTime",
-            "type": "i64"
+            "name": "rateVersion",
+            "type": "u64"
+          },
+          {
+            "name": "rateHistoryCount",
+            "type": "u64"
+          },
+          {
+            "name": "rateHistory",
+            "type": {
+              "array": [
+                {
+                  "defined": "RateCheckpoint"
+                },
+                8
+              ]
+            }
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "merkleRoot",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "rarityRoot",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
           }
         ]
       }
     },
     {
-      "name": "StakingError",
+      "name": "stakeReceipt",
       "type": {
-        "kind": "enum",
-        "variants": [
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "owner",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "stake",
+            "type": {
+              "defined": "StakedData"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "receiptCounter",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "count",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "userPool",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "owner",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "staking",
+            "type": {
+              "vec": {
+                "defined": "StakedData"
+              }
+            }
+          }
+        ]
+      }
+    }
+  ],
+  "types": [
+    {
+      "name": "RoleGrant",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "operator",
+            "type": "publicKey"
+          },
+          {
+            "name": "role",
+            "type": {
+              "defined": "Role"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "RarityProof",
+      "type": {
+        "kind": "struct",
+        "fields": [
           {
-            "name": "InvalidSuperOwner"
+            "name": "weightBps",
+            "type": "u64"
+          },
+          {
+            "name": "proof",
+            "type": {
+              "vec": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "RateCheckpoint",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "endTime",
+            "type": "i64"
+          },
+          {
+            "name": "tierCount",
+            "type": "u64"
+          },
+          {
+            "name": "tierDuration",
+            "type": {
+              "array": [
+                "i64",
+                8
+              ]
+            }
           },
           {
-            "name": "InvalidInput"
+            "name": "tierRate",
+            "type": {
+              "array": [
+                "u64",
+                8
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "StakedData",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "publicKey"
           },
           {
-            "name": "InvalidWhitelistAddress"
+            "name": "farm",
+            "type": "publicKey"
           },
           {
-            "name": "InvalidFarmCount"
+            "name": "farmNumber",
+            "type": "u64"
           },
           {
-            "name": "InvalidGlobalPool"
+            "name": "stakedTime",
+            "type": "i64"
           },
           {
-            "name": "InvalidUserPool"
+            "name": "claimedTime",
+            "type": "i64"
           },
           {
-            "name": "PoolStopped"
+            "name": "unstakeRequestedTime",
+            "type": "i64"
           },
           {
-            "name": "ExceedMaxCount"
+            "name": "multiplierBps",
+            "type": "u64"
           },
           {
-            "name": "InvalidWithdrawTime"
+            "name": "rarityWeightBps",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "WhitelistKind",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Mint"
           },
           {
-            "name": "InvalidNFTAddress"
+            "name": "Collection"
           },
           {
-            "name": "InsufficientRewardVault"
+            "name": "UpdateAuthority"
           },
           {
-            "name": "InvaliedMetadata"
+            "name": "Deny"
+          }
+        ]
+      }
+    },
+    {
+      "name": "Role",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "FarmManager"
           },
           {
-            "name": "MetadataCreatorParseError"
+            "name": "WhitelistManager"
           },
           {
-            "name": "UnkownOrNotAllowedNFTCollection"
+            "name": "TreasuryManager"
           }
         ]
       }
     }
+  ],
+  "events": [
+    {
+      "name": "InitializeEvent",
+      "fields": [
+        {
+          "name": "superAdmin",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "rewardToken",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "ProposeAdminEvent",
+      "fields": [
+        {
+          "name": "superAdmin",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "proposedAdmin",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "CancelAdminProposalEvent",
+      "fields": [
+        {
+          "name": "superAdmin",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "proposedAdmin",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "AcceptAdminEvent",
+      "fields": [
+        {
+          "name": "previousAdmin",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "newAdmin",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "GrantRoleEvent",
+      "fields": [
+        {
+          "name": "operator",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "role",
+          "type": {
+            "defined": "Role"
+          },
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RevokeRoleEvent",
+      "fields": [
+        {
+          "name": "operator",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "role",
+          "type": {
+            "defined": "Role"
+          },
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "CloseUserPoolEvent",
+      "fields": [
+        {
+          "name": "owner",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "userPool",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "CreateFarmEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "farm",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "rewardMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "tierDuration",
+          "type": {
+            "vec": "i64"
+          },
+          "index": false
+        },
+        {
+          "name": "tierRate",
+          "type": {
+            "vec": "u64"
+          },
+          "index": false
+        },
+        {
+          "name": "maxStakeCount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "minLockDuration",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "cooldownDuration",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "UpdateFarmEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "farm",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "tierDuration",
+          "type": {
+            "vec": "i64"
+          },
+          "index": false
+        },
+        {
+          "name": "tierRate",
+          "type": {
+            "vec": "u64"
+          },
+          "index": false
+        },
+        {
+          "name": "maxStakeCount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "minLockDuration",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "cooldownDuration",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "rateVersion",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "AddWhitelistEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "whitelistAddress",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "kind",
+          "type": {
+            "defined": "WhitelistKind"
+          },
+          "index": false
+        },
+        {
+          "name": "multiplierBps",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RemoveWhitelistEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "whitelistAddress",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "kind",
+          "type": {
+            "defined": "WhitelistKind"
+          },
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "SetStopEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "isStop",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "SetMerkleRootEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "merkleRoot",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          },
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "SetRarityRootEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "rarityRoot",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          },
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "StakeEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "owner",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "multiplierBps",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "rarityWeightBps",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "BeginUnstakeEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "owner",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "cooldownEndTime",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "UnstakeEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "owner",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "reward",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "penalty",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "unpaidReward",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "UnstakeLegacyNftEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "owner",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "ClaimEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "owner",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "reward",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "MigrateToReceiptEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "owner",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "stakeReceipt",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "ClaimAllEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": {
+            "option": "u64"
+          },
+          "index": false
+        },
+        {
+          "name": "owner",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "reward",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "SetUnstakePenaltyEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "penaltyDuration",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "penaltyBps",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "penaltyTreasury",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "InitRewardVaultEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "rewardVault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "rewardMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "FundRewardVaultEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "rewardVault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "admin",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "TransferMintAuthorityEvent",
+      "fields": [
+        {
+          "name": "rewardMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "newAuthority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "MintToAccountEvent",
+      "fields": [
+        {
+          "name": "rewardMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "userRewardAccount",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "admin",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    }
+  ],
+  "errors": [
+    {
+      "code": 6000,
+      "name": "InvalidSuperOwner",
+      "msg": "Invalid Super Owner"
+    },
+    {
+      "code": 6001,
+      "name": "InvalidInput",
+      "msg": "Invalid Input Data"
+    },
+    {
+      "code": 6002,
+      "name": "InvalidWhitelistAddress",
+      "msg": "Invalid Whitelist Address"
+    },
+    {
+      "code": 6003,
+      "name": "InvalidFarmCount",
+      "msg": "Invalid Farm Count"
+    },
+    {
+      "code": 6004,
+      "name": "InvalidGlobalPool",
+      "msg": "Invalid Global Pool Address"
+    },
+    {
+      "code": 6005,
+      "name": "InvalidUserPool",
+      "msg": "Invalid User Pool Owner Address"
+    },
+    {
+      "code": 6006,
+      "name": "PoolStopped",
+      "msg": "This Farm Pool is Stopped"
+    },
+    {
+      "code": 6007,
+      "name": "ExceedMaxCount",
+      "msg": "Exceed the Max Count of this Pool"
+    },
+    {
+      "code": 6008,
+      "name": "InvalidWithdrawTime",
+      "msg": "Invalid Withdraw Time"
+    },
+    {
+      "code": 6009,
+      "name": "InvalidNFTAddress",
+      "msg": "Not Found Staked Mint"
+    },
+    {
+      "code": 6010,
+      "name": "InsufficientRewardVault",
+      "msg": "Insufficient Reward Token Balance"
+    },
+    {
+      "code": 6011,
+      "name": "InvaliedMetadata",
+      "msg": "Invalid Metadata Address"
+    },
+    {
+      "code": 6012,
+      "name": "MetadataCreatorParseError",
+      "msg": "Can't Parse The NFT's Creators"
+    },
+    {
+      "code": 6013,
+      "name": "UnkownOrNotAllowedNFTCollection",
+      "msg": "Unknown Collection Or The Collection Is Not Allowed"
+    },
+    {
+      "code": 6014,
+      "name": "RewardOverflow",
+      "msg": "Reward Calculation Overflow"
+    },
+    {
+      "code": 6015,
+      "name": "MissingFarmAccount",
+      "msg": "Farm Account Of A Staked NFT Is Not Provided"
+    },
+    {
+      "code": 6016,
+      "name": "InvalidRewardVault",
+      "msg": "Invalid Reward Vault Address"
+    },
+    {
+      "code": 6017,
+      "name": "InvalidRewardMint",
+      "msg": "Invalid Reward Mint Of The Farm"
+    },
+    {
+      "code": 6018,
+      "name": "InvalidTierCount",
+      "msg": "Tier Count Must Be Between 1 And The Max Tier Count"
+    },
+    {
+      "code": 6019,
+      "name": "TierLengthMismatch",
+      "msg": "Tier Durations And Rates Have Different Lengths"
+    },
+    {
+      "code": 6020,
+      "name": "InvalidTierDuration",
+      "msg": "Tier Durations Must Be Positive"
+    },
+    {
+      "code": 6021,
+      "name": "TierRateTooHigh",
+      "msg": "Tier Rate Exceeds The Max Rate"
+    },
+    {
+      "code": 6022,
+      "name": "InvalidPenaltyTreasury",
+      "msg": "Invalid Penalty Treasury Address"
+    },
+    {
+      "code": 6023,
+      "name": "CooldownRequired",
+      "msg": "This Farm Requires Begin Unstake And A Cooldown"
+    },
+    {
+      "code": 6024,
+      "name": "UnstakeAlreadyRequested",
+      "msg": "Unstake Is Already Requested"
+    },
+    {
+      "code": 6025,
+      "name": "UnstakeNotRequested",
+      "msg": "Unstake Is Not Requested"
+    },
+    {
+      "code": 6026,
+      "name": "InvalidFarmPool",
+      "msg": "Staked NFT Doesn't Belong To This Farm"
+    },
+    {
+      "code": 6027,
+      "name": "UserPoolNotEmpty",
+      "msg": "User Pool Still Has Staked NFTs"
+    },
+    {
+      "code": 6028,
+      "name": "UnverifiedCollection",
+      "msg": "NFT Has No Verified Collection Or Creator"
+    },
+    {
+      "code": 6029,
+      "name": "InvalidMerkleProof",
+      "msg": "Invalid Merkle Proof Of The NFT"
+    },
+    {
+      "code": 6030,
+      "name": "DeniedNFT",
+      "msg": "NFT Is Denied In This Farm"
+    },
+    {
+      "code": 6031,
+      "name": "InvalidRewardMultiplier",
+      "msg": "Reward Multiplier Must Be Between 1 And The Max Multiplier"
+    },
+    {
+      "code": 6032,
+      "name": "InvalidRarityProof",
+      "msg": "Invalid Rarity Proof Of The NFT"
+    },
+    {
+      "code": 6033,
+      "name": "MissingRole",
+      "msg": "Signer Doesn't Have The Required Role"
+    },
+    {
+      "code": 6034,
+      "name": "RoleAlreadyGranted",
+      "msg": "Role Is Already Granted To The Operator"
+    },
+    {
+      "code": 6035,
+      "name": "RoleNotGranted",
+      "msg": "Role Isn't Granted To The Operator"
+    },
+    {
+      "code": 6036,
+      "name": "TooManyRoleGrants",
+      "msg": "Exceed Max Role Grant Count"
+    },
+    {
+      "code": 6037,
+      "name": "InvalidProposedAdmin",
+      "msg": "Signer Isn't The Proposed Admin"
+    },
+    {
+      "code": 6038,
+      "name": "RateHistoryFull",
+      "msg": "Farm Rate History Is Full"
+    },
+    {
+      "code": 6039,
+      "name": "ClaimInPenaltyPeriod",
+      "msg": "Reward Can't Be Claimed During The Early Unstake Penalty Period"
+    },
+    {
+      "code": 6040,
+      "name": "InvalidRarityWeight",
+      "msg": "Rarity Weight Must Be Between 1 And The Max Weight"
+    },
+    {
+      "code": 6041,
+      "name": "MissingDenyAccount",
+      "msg": "Deny Entry Address Of The NFT Is Missing"
+    }
+  ]
+};
+
+export const IDL: Staking = {
+  "version": "0.1.0",
+  "name": "staking",
+  "instructions": [
+    {
+      "name": "initialize",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardToken",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "globalBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "proposeAdmin",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminProposal",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "newAdmin",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "cancelAdminProposal",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminProposal",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "acceptAdmin",
+      "accounts": [
+        {
+          "name": "newAdmin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "adminProposal",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "initAdminRoles",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminRoles",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "grantRole",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminRoles",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "operator",
+          "type": "publicKey"
+        },
+        {
+          "name": "role",
+          "type": {
+            "defined": "Role"
+          }
+        }
+      ]
+    },
+    {
+      "name": "revokeRole",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminRoles",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "operator",
+          "type": "publicKey"
+        },
+        {
+          "name": "role",
+          "type": {
+            "defined": "Role"
+          }
+        }
+      ]
+    },
+    {
+      "name": "initializeUserPool",
+      "accounts": [
+        {
+          "name": "userPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "closeUserPool",
+      "accounts": [
+        {
+          "name": "userPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "createFarm",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "adminRoles",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "farmPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "tierDuration",
+          "type": {
+            "vec": "i64"
+          }
+        },
+        {
+          "name": "tierRate",
+          "type": {
+            "vec": "u64"
+          }
+        },
+        {
+          "name": "maxStakeCount",
+          "type": "u64"
+        },
+        {
+          "name": "minLockDuration",
+          "type": "i64"
+        },
+        {
+          "name": "cooldownDuration",
+          "type": "i64"
+        },
+        {
+          "name": "count",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "updateFarm",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "adminRoles",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "farmPool",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "newTierDuration",
+          "type": {
+            "vec": "i64"
+          }
+        },
+        {
+          "name": "newTierRate",
+          "type": {
+            "vec": "u64"
+          }
+        },
+        {
+          "name": "maxStakeCount",
+          "type": "u64"
+        },
+        {
+          "name": "minLockDuration",
+          "type": "i64"
+        },
+        {
+          "name": "cooldownDuration",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "addWhitelist",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "adminRoles",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "farmPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "whitelistAddress",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "whitelistProof",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "kind",
+          "type": {
+            "defined": "WhitelistKind"
+          }
+        },
+        {
+          "name": "multiplierBps",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "removeWhitelist",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "adminRoles",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "farmPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "whitelistAddress",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "whitelistProof",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setStop",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "adminRoles",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "farmPool",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "isStop",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "setMerkleRoot",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminRoles",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "farmPool",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "merkleRoot",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "setRarityRoot",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminRoles",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "farmPool",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "rarityRoot",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "stakeNftToPool",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "userPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "receiptCounter",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "farmPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userNftTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destNftTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "nftMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mintMetadata",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMetadataProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "globalBump",
+          "type": "u8"
+        },
+        {
+          "name": "merkleProof",
+          "type": {
+            "option": {
+              "vec": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
+            }
+          }
+        },
+        {
+          "name": "rarity",
+          "type": {
+            "option": {
+              "defined": "RarityProof"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "unstakeNftFromPool",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "userPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "farmPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userNftTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destNftTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userRewardAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "penaltyTreasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "nftMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "globalBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "unstakeLegacyNft",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "legacyUserPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userNftTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destNftTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "nftMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "globalBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "beginUnstake",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "userPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "farmPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "nftMint",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "finalizeUnstake",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "userPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "farmPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userNftTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destNftTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userRewardAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "penaltyTreasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "nftMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "globalBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "claimReward",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "userPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "farmPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userRewardAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "nftMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "globalBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "claimAll",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "userPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userRewardAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "globalBump",
+          "type": "u8"
+        },
+        {
+          "name": "farmNumber",
+          "type": {
+            "option": "u64"
+          }
+        }
+      ]
+    },
+    {
+      "name": "stakeNftToReceipt",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "stakeReceipt",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "receiptCounter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "farmPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userNftTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destNftTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "nftMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mintMetadata",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMetadataProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "globalBump",
+          "type": "u8"
+        },
+        {
+          "name": "merkleProof",
+          "type": {
+            "option": {
+              "vec": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
+            }
+          }
+        },
+        {
+          "name": "rarity",
+          "type": {
+            "option": {
+              "defined": "RarityProof"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "beginReceiptUnstake",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "stakeReceipt",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "farmPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "nftMint",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "unstakeNftFromReceipt",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "stakeReceipt",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "receiptCounter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "farmPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userNftTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destNftTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userRewardAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "penaltyTreasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "nftMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "globalBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "claimReceiptReward",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "stakeReceipt",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "farmPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userRewardAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "nftMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "globalBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "migrateToReceipt",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "userPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "stakeReceipt",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "receiptCounter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "farmPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "nftMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setUnstakePenalty",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminRoles",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "farmPool",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "penaltyDuration",
+          "type": "i64"
+        },
+        {
+          "name": "penaltyBps",
+          "type": "u64"
+        },
+        {
+          "name": "penaltyTreasury",
+          "type": {
+            "option": "publicKey"
+          }
+        }
+      ]
+    },
+    {
+      "name": "initRewardVault",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminRoles",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "farmPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rewardVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "fundRewardVault",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminRoles",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "farmPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminRewardAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "transferMintAuthority",
+      "accounts": [
+        {
+          "name": "globalAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rewardMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "newAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "globalBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "mintToAccount",
+      "accounts": [
+        {
+          "name": "globalAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "adminRoles",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rewardMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "userRewardAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "globalBump",
+          "type": "u8"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "globalPool",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "superAdmin",
+            "type": "publicKey"
+          },
+          {
+            "name": "rewardToken",
+            "type": "publicKey"
+          },
+          {
+            "name": "totalStakedCount",
+            "type": "u64"
+          },
+          {
+            "name": "farmCount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "adminProposal",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "proposedAdmin",
+            "type": "publicKey"
+          },
+          {
+            "name": "proposedTime",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "adminRoles",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "grants",
+            "type": {
+              "vec": {
+                "defined": "RoleGrant"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "whitelistProof",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "whitelistAddress",
+            "type": "publicKey"
+          },
+          {
+            "name": "kind",
+            "type": {
+              "defined": "WhitelistKind"
+            }
+          },
+          {
+            "name": "multiplierBps",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "farmData",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "farmNumber",
+            "type": "u64"
+          },
+          {
+            "name": "stakedCount",
+            "type": "u64"
+          },
+          {
+            "name": "maxStakeCount",
+            "type": "u64"
+          },
+          {
+            "name": "tierCount",
+            "type": "u64"
+          },
+          {
+            "name": "tierDuration",
+            "type": {
+              "array": [
+                "i64",
+                8
+              ]
+            }
+          },
+          {
+            "name": "tierRate",
+            "type": {
+              "array": [
+                "u64",
+                8
+              ]
+            }
+          },
+          {
+            "name": "isStop",
+            "type": "bool"
+          },
+          {
+            "name": "rewardVault",
+            "type": "publicKey"
+          },
+          {
+            "name": "rewardMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "minLockDuration",
+            "type": "i64"
+          },
+          {
+            "name": "cooldownDuration",
+            "type": "i64"
+          },
+          {
+            "name": "penaltyDuration",
+            "type": "i64"
+          },
+          {
+            "name": "penaltyBps",
+            "type": "u64"
+          },
+          {
+            "name": "penaltyTreasury",
+            "type": "publicKey"
+          },
+          {
+            "name": "rateVersion",
+            "type": "u64"
+          },
+          {
+            "name": "rateHistoryCount",
+            "type": "u64"
+          },
+          {
+            "name": "rateHistory",
+            "type": {
+              "array": [
+                {
+                  "defined": "RateCheckpoint"
+                },
+                8
+              ]
+            }
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "merkleRoot",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "rarityRoot",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "stakeReceipt",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "owner",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "stake",
+            "type": {
+              "defined": "StakedData"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "receiptCounter",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "count",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "userPool",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "owner",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "staking",
+            "type": {
+              "vec": {
+                "defined": "StakedData"
+              }
+            }
+          }
+        ]
+      }
+    }
+  ],
+  "types": [
+    {
+      "name": "RoleGrant",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "operator",
+            "type": "publicKey"
+          },
+          {
+            "name": "role",
+            "type": {
+              "defined": "Role"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "RarityProof",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "weightBps",
+            "type": "u64"
+          },
+          {
+            "name": "proof",
+            "type": {
+              "vec": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "RateCheckpoint",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "endTime",
+            "type": "i64"
+          },
+          {
+            "name": "tierCount",
+            "type": "u64"
+          },
+          {
+            "name": "tierDuration",
+            "type": {
+              "array": [
+                "i64",
+                8
+              ]
+            }
+          },
+          {
+            "name": "tierRate",
+            "type": {
+              "array": [
+                "u64",
+                8
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "StakedData",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "farm",
+            "type": "publicKey"
+          },
+          {
+            "name": "farmNumber",
+            "type": "u64"
+          },
+          {
+            "name": "stakedTime",
+            "type": "i64"
+          },
+          {
+            "name": "claimedTime",
+            "type": "i64"
+          },
+          {
+            "name": "unstakeRequestedTime",
+            "type": "i64"
+          },
+          {
+            "name": "multiplierBps",
+            "type": "u64"
+          },
+          {
+            "name": "rarityWeightBps",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "WhitelistKind",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Mint"
+          },
+          {
+            "name": "Collection"
+          },
+          {
+            "name": "UpdateAuthority"
+          },
+          {
+            "name": "Deny"
+          }
+        ]
+      }
+    },
+    {
+      "name": "Role",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "FarmManager"
+          },
+          {
+            "name": "WhitelistManager"
+          },
+          {
+            "name": "TreasuryManager"
+          }
+        ]
+      }
+    }
+  ],
+  "events": [
+    {
+      "name": "InitializeEvent",
+      "fields": [
+        {
+          "name": "superAdmin",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "rewardToken",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "ProposeAdminEvent",
+      "fields": [
+        {
+          "name": "superAdmin",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "proposedAdmin",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "CancelAdminProposalEvent",
+      "fields": [
+        {
+          "name": "superAdmin",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "proposedAdmin",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "AcceptAdminEvent",
+      "fields": [
+        {
+          "name": "previousAdmin",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "newAdmin",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "GrantRoleEvent",
+      "fields": [
+        {
+          "name": "operator",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "role",
+          "type": {
+            "defined": "Role"
+          },
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RevokeRoleEvent",
+      "fields": [
+        {
+          "name": "operator",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "role",
+          "type": {
+            "defined": "Role"
+          },
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "CloseUserPoolEvent",
+      "fields": [
+        {
+          "name": "owner",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "userPool",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "CreateFarmEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "farm",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "rewardMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "tierDuration",
+          "type": {
+            "vec": "i64"
+          },
+          "index": false
+        },
+        {
+          "name": "tierRate",
+          "type": {
+            "vec": "u64"
+          },
+          "index": false
+        },
+        {
+          "name": "maxStakeCount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "minLockDuration",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "cooldownDuration",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "UpdateFarmEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "farm",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "tierDuration",
+          "type": {
+            "vec": "i64"
+          },
+          "index": false
+        },
+        {
+          "name": "tierRate",
+          "type": {
+            "vec": "u64"
+          },
+          "index": false
+        },
+        {
+          "name": "maxStakeCount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "minLockDuration",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "cooldownDuration",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "rateVersion",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "AddWhitelistEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "whitelistAddress",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "kind",
+          "type": {
+            "defined": "WhitelistKind"
+          },
+          "index": false
+        },
+        {
+          "name": "multiplierBps",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RemoveWhitelistEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "whitelistAddress",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "kind",
+          "type": {
+            "defined": "WhitelistKind"
+          },
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "SetStopEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "isStop",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "SetMerkleRootEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "merkleRoot",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          },
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "SetRarityRootEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "rarityRoot",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          },
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "StakeEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "owner",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "multiplierBps",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "rarityWeightBps",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "BeginUnstakeEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "owner",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "cooldownEndTime",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "UnstakeEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "owner",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "reward",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "penalty",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "unpaidReward",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "UnstakeLegacyNftEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "owner",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "ClaimEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "owner",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "reward",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "MigrateToReceiptEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "owner",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "stakeReceipt",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "ClaimAllEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": {
+            "option": "u64"
+          },
+          "index": false
+        },
+        {
+          "name": "owner",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "reward",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "SetUnstakePenaltyEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "penaltyDuration",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "penaltyBps",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "penaltyTreasury",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "InitRewardVaultEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "rewardVault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "rewardMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "FundRewardVaultEvent",
+      "fields": [
+        {
+          "name": "farmNumber",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "rewardVault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "admin",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "TransferMintAuthorityEvent",
+      "fields": [
+        {
+          "name": "rewardMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "newAuthority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "MintToAccountEvent",
+      "fields": [
+        {
+          "name": "rewardMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "userRewardAccount",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "admin",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    }
+  ],
+  "errors": [
+    {
+      "code": 6000,
+      "name": "InvalidSuperOwner",
+      "msg": "Invalid Super Owner"
+    },
+    {
+      "code": 6001,
+      "name": "InvalidInput",
+      "msg": "Invalid Input Data"
+    },
+    {
+      "code": 6002,
+      "name": "InvalidWhitelistAddress",
+      "msg": "Invalid Whitelist Address"
+    },
+    {
+      "code": 6003,
+      "name": "InvalidFarmCount",
+      "msg": "Invalid Farm Count"
+    },
+    {
+      "code": 6004,
+      "name": "InvalidGlobalPool",
+      "msg": "Invalid Global Pool Address"
+    },
+    {
+      "code": 6005,
+      "name": "InvalidUserPool",
+      "msg": "Invalid User Pool Owner Address"
+    },
+    {
+      "code": 6006,
+      "name": "PoolStopped",
+      "msg": "This Farm Pool is Stopped"
+    },
+    {
+      "code": 6007,
+      "name": "ExceedMaxCount",
+      "msg": "Exceed the Max Count of this Pool"
+    },
+    {
+      "code": 6008,
+      "name": "InvalidWithdrawTime",
+      "msg": "Invalid Withdraw Time"
+    },
+    {
+      "code": 6009,
+      "name": "InvalidNFTAddress",
+      "msg": "Not Found Staked Mint"
+    },
+    {
+      "code": 6010,
+      "name": "InsufficientRewardVault",
+      "msg": "Insufficient Reward Token Balance"
+    },
+    {
+      "code": 6011,
+      "name": "InvaliedMetadata",
+      "msg": "Invalid Metadata Address"
+    },
+    {
+      "code": 6012,
+      "name": "MetadataCreatorParseError",
+      "msg": "Can't Parse The NFT's Creators"
+    },
+    {
+      "code": 6013,
+      "name": "UnkownOrNotAllowedNFTCollection",
+      "msg": "Unknown Collection Or The Collection Is Not Allowed"
+    },
+    {
+      "code": 6014,
+      "name": "RewardOverflow",
+      "msg": "Reward Calculation Overflow"
+    },
+    {
+      "code": 6015,
+      "name": "MissingFarmAccount",
+      "msg": "Farm Account Of A Staked NFT Is Not Provided"
+    },
+    {
+      "code": 6016,
+      "name": "InvalidRewardVault",
+      "msg": "Invalid Reward Vault Address"
+    },
+    {
+      "code": 6017,
+      "name": "InvalidRewardMint",
+      "msg": "Invalid Reward Mint Of The Farm"
+    },
+    {
+      "code": 6018,
+      "name": "InvalidTierCount",
+      "msg": "Tier Count Must Be Between 1 And The Max Tier Count"
+    },
+    {
+      "code": 6019,
+      "name": "TierLengthMismatch",
+      "msg": "Tier Durations And Rates Have Different Lengths"
+    },
+    {
+      "code": 6020,
+      "name": "InvalidTierDuration",
+      "msg": "Tier Durations Must Be Positive"
+    },
+    {
+      "code": 6021,
+      "name": "TierRateTooHigh",
+      "msg": "Tier Rate Exceeds The Max Rate"
+    },
+    {
+      "code": 6022,
+      "name": "InvalidPenaltyTreasury",
+      "msg": "Invalid Penalty Treasury Address"
+    },
+    {
+      "code": 6023,
+      "name": "CooldownRequired",
+      "msg": "This Farm Requires Begin Unstake And A Cooldown"
+    },
+    {
+      "code": 6024,
+      "name": "UnstakeAlreadyRequested",
+      "msg": "Unstake Is Already Requested"
+    },
+    {
+      "code": 6025,
+      "name": "UnstakeNotRequested",
+      "msg": "Unstake Is Not Requested"
+    },
+    {
+      "code": 6026,
+      "name": "InvalidFarmPool",
+      "msg": "Staked NFT Doesn't Belong To This Farm"
+    },
+    {
+      "code": 6027,
+      "name": "UserPoolNotEmpty",
+      "msg": "User Pool Still Has Staked NFTs"
+    },
+    {
+      "code": 6028,
+      "name": "UnverifiedCollection",
+      "msg": "NFT Has No Verified Collection Or Creator"
+    },
+    {
+      "code": 6029,
+      "name": "InvalidMerkleProof",
+      "msg": "Invalid Merkle Proof Of The NFT"
+    },
+    {
+      "code": 6030,
+      "name": "DeniedNFT",
+      "msg": "NFT Is Denied In This Farm"
+    },
+    {
+      "code": 6031,
+      "name": "InvalidRewardMultiplier",
+      "msg": "Reward Multiplier Must Be Between 1 And The Max Multiplier"
+    },
+    {
+      "code": 6032,
+      "name": "InvalidRarityProof",
+      "msg": "Invalid Rarity Proof Of The NFT"
+    },
+    {
+      "code": 6033,
+      "name": "MissingRole",
+      "msg": "Signer Doesn't Have The Required Role"
+    },
+    {
+      "code": 6034,
+      "name": "RoleAlreadyGranted",
+      "msg": "Role Is Already Granted To The Operator"
+    },
+    {
+      "code": 6035,
+      "name": "RoleNotGranted",
+      "msg": "Role Isn't Granted To The Operator"
+    },
+    {
+      "code": 6036,
+      "name": "TooManyRoleGrants",
+      "msg": "Exceed Max Role Grant Count"
+    },
+    {
+      "code": 6037,
+      "name": "InvalidProposedAdmin",
+      "msg": "Signer Isn't The Proposed Admin"
+    },
+    {
+      "code": 6038,
+      "name": "RateHistoryFull",
+      "msg": "Farm Rate History Is Full"
+    },
+    {
+      "code": 6039,
+      "name": "ClaimInPenaltyPeriod",
+      "msg": "Reward Can't Be Claimed During The Early Unstake Penalty Period"
+    },
+    {
+      "code": 6040,
+      "name": "InvalidRarityWeight",
+      "msg": "Rarity Weight Must Be Between 1 And The Max Weight"
+    },
+    {
+      "code": 6041,
+      "name": "MissingDenyAccount",
+      "msg": "Deny Entry Address Of The NFT Is Missing"
+    }
   ]
 };
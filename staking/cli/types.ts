@@ -2,11 +2,19 @@ import * as anchor from '@project-serum/anchor';
 import { PublicKey } from '@solana/web3.js';
 
 export const GLOBAL_AUTHORITY_SEED = "global-authority";
+export const USER_POOL_SEED = "user-pool";
+export const STAKE_RECEIPT_SEED = "stake-receipt";
+export const RECEIPT_COUNTER_SEED = "receipt-counter";
+// Seed of the first version's user pools, created with createAccountWithSeed instead of as PDAs
+export const LEGACY_USER_POOL_SEED = "user-pool";
+export const FARM_SEED = "farm";
 
 export const STAKING_PROGRAM_ID = new PublicKey("3nJ33QN3CnVuqUVJc9URnRDVbSMSSp14sb45p4PVUyE9");
 export const DECIMALS = 1000000000;
-export const USER_POOL_SIZE = 5648;     // 8 + 5640
-export const FARM_POOL_SIZE = 104;
+export const USER_POOL_SIZE = 45;       // 8 + 37, grows by 112 per staked NFT
+export const STAKE_RECEIPT_SIZE = 153;  // 8 + 145
+export const LEGACY_USER_POOL_SIZE = 5648;  // 8 + 40 + 56 * 100
+export const FARM_POOL_SIZE = 1544;
 
 export interface GlobalPool {
     // 8 + 40
@@ -16,9 +24,63 @@ export interface GlobalPool {
     farmCount: anchor.BN,              // 8
 }
 
+export const MINT_WHITELIST_SEED = "mint";
+export const COLLECTION_WHITELIST_SEED = "collection";
+export const UPDATE_AUTHORITY_WHITELIST_SEED = "update-authority";
+export const DENYLIST_SEED = "denylist";
+
+// Order of the program's WhitelistKind
+export enum WhitelistKind {
+    Mint,
+    Collection,
+    UpdateAuthority,
+    Deny,
+}
+
+export const ADMIN_PROPOSAL_SEED = "admin-proposal";
+
+export interface AdminProposal {
+    // 8 + 40
+    proposedAdmin: PublicKey,               // 32
+    proposedTime: anchor.BN,                // 8
+}
+
+export const ADMIN_ROLES_SEED = "admin-roles";
+export const ADMIN_ROLES_SIZE = 541;    // 8 + 5 + 33 * 16 grants
+
+// Order of the program's Role
+export enum Role {
+    FarmManager,
+    WhitelistManager,
+    TreasuryManager,
+}
+
+export interface RoleGrant {
+    operator: PublicKey,                    // 32
+    role: object,                           // 1, Role variant
+}
+
+export interface AdminRoles {
+    bump: number,                           // 1
+    grants: RoleGrant[],                    // 4 + 33 * grant count
+}
+
 export interface WhitelistProof {
     whitelistAddress: PublicKey,            // 32
-    isCollection: Boolean,                  // 1
+    kind: object,                           // 1, WhitelistKind variant
+    multiplierBps: anchor.BN,               // 8, missing from entries stored before the multipliers
+}
+
+export interface RarityProof {
+    weightBps: anchor.BN,
+    proof: number[][],
+}
+
+export interface RateCheckpoint {
+    endTime: anchor.BN,
+    tierCount: anchor.BN,
+    tierDuration: anchor.BN[],
+    tierRate: anchor.BN[],
 }
 
 export interface FarmData {
@@ -26,22 +88,67 @@ export interface FarmData {
     farmNumber: anchor.BN,
     stakedCount: anchor.BN,
     maxStakeCount: anchor.BN,
+    tierCount: anchor.BN,
     tierDuration: anchor.BN[],
     tierRate: anchor.BN[],
     isStop: Boolean,
+    rewardVault: PublicKey,
+    rewardMint: PublicKey,
+    minLockDuration: anchor.BN,
+    cooldownDuration: anchor.BN,
+    penaltyDuration: anchor.BN,
+    penaltyBps: anchor.BN,
+    penaltyTreasury: PublicKey,
+    rateVersion: anchor.BN,
+    rateHistoryCount: anchor.BN,
+    rateHistory: RateCheckpoint[],
+    bump: number,
+    merkleRoot: number[],
+    rarityRoot: number[],
 }
 
 
 export interface StakedData {
     mint: PublicKey,            // 32
+    farm: PublicKey,            // 32
     farmNumber: anchor.BN,      // 8
     stakedTime: anchor.BN,      // 8
     claimedTime: anchor.BN,     // 8
+    unstakeRequestedTime: anchor.BN,    // 8
+    multiplierBps: anchor.BN,           // 8
+    rarityWeightBps: anchor.BN,         // 8
 }
 
 export interface UserPool {
+    // 8 + 37 + 112 * staked count
+    owner: PublicKey,               // 32
+    bump: number,                   // 1
+    staking: StakedData[],          // 4 + 112 * staked count
+}
+
+export interface LegacyStakedData {
+    mint: PublicKey,            // 32
+    farmNumber: anchor.BN,      // 8
+    stakedTime: anchor.BN,      // 8
+    claimedTime: anchor.BN,     // 8
+}
+
+export interface LegacyUserPool {
     // 8 + 5640
+    owner: PublicKey,                       // 32
+    stakedCount: anchor.BN,                 // 8
+    staking: LegacyStakedData[],            // 56 * 100
+}
+
+export interface StakeReceipt {
+    // 8 + 145
     owner: PublicKey,               // 32
-    stakedCount: anchor.BN,         // 8
-    staking: StakedData[],          // 56 * 100
-}
\ No newline at end of file
+    bump: number,                   // 1
+    stake: StakedData,              // 112
+}
+
+export interface ReceiptCounter {
+    // 8 + 9
+    bump: number,                   // 1
+    count: anchor.BN,               // 8
+}
@@ -0,0 +1,170 @@
+//! Off-chain side of the farms' merkle allowlists and rarity tables.
+//! The root of a mint list is set with `set_merkle_root`, the root of a rarity table with `set_rarity_root`.
+//! A mint's proofs are passed to `stake_nft_to_pool` when staking it.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::pubkey::ParsePubkeyError;
+use staking::account::RarityProof;
+use staking::constants::MAX_RARITY_WEIGHT_BPS;
+use staking::merkle::{leaf_hash, node_hash, rarity_leaf_hash};
+
+/// Parses a hashlist with one base58 mint address per line, blank lines are skipped
+pub fn parse_mint_list(text: &str) -> Result<Vec<Pubkey>, ParsePubkeyError> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(Pubkey::from_str)
+        .collect()
+}
+
+/// Invalid rarity table, the CSV errors carry the line number and the table errors the mint
+#[derive(Debug, PartialEq)]
+pub enum RarityError {
+    MissingWeight(usize),
+    InvalidMint(usize),
+    InvalidWeight(usize),
+    WeightOutOfBounds(Pubkey),
+    DuplicateMint(Pubkey),
+}
+
+impl fmt::Display for RarityError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RarityError::MissingWeight(line) => write!(f, "line {}: expected `mint,weight_bps`", line),
+            RarityError::InvalidMint(line) => write!(f, "line {}: invalid mint address", line),
+            RarityError::InvalidWeight(line) => {
+                write!(f, "line {}: weight must be an integer between 1 and {}", line, MAX_RARITY_WEIGHT_BPS)
+            }
+            RarityError::WeightOutOfBounds(mint) => {
+                write!(f, "mint {}: weight must be between 1 and {}", mint, MAX_RARITY_WEIGHT_BPS)
+            }
+            RarityError::DuplicateMint(mint) => write!(f, "mint {} is listed twice", mint),
+        }
+    }
+}
+
+impl std::error::Error for RarityError {}
+
+fn valid_weight(weight_bps: u64) -> bool {
+    weight_bps > 0 && weight_bps <= MAX_RARITY_WEIGHT_BPS
+}
+
+/// Parses a rarity CSV with one `mint,weight_bps` row per line, a weight of 10000 earns the farm's rate.
+/// Blank lines and a leading `mint,...` header are skipped, line numbers in errors start at 1.
+pub fn parse_rarity_csv(text: &str) -> Result<Vec<(Pubkey, u64)>, RarityError> {
+    let mut weights = BTreeMap::new();
+    let mut rows = vec![];
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+        if line.is_empty() || (rows.is_empty() && line.starts_with("mint")) {
+            continue;
+        }
+
+        let (mint, weight) = line.split_once(',')
+            .ok_or(RarityError::MissingWeight(line_number))?;
+        let mint = Pubkey::from_str(mint.trim())
+            .map_err(|_| RarityError::InvalidMint(line_number))?;
+        let weight = weight.trim().parse::<u64>().ok()
+            .filter(|weight| valid_weight(*weight))
+            .ok_or(RarityError::InvalidWeight(line_number))?;
+        if weights.insert(mint, weight).is_some() {
+            return Err(RarityError::DuplicateMint(mint));
+        }
+        rows.push((mint, weight));
+    }
+    Ok(rows)
+}
+
+pub struct MerkleTree {
+    // Sorted leaves first, the root last
+    layers: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    /// Tree of the allowed mints, duplicated mints are only added once
+    pub fn new(mints: &[Pubkey]) -> Self {
+        Self::from_leaves(mints.iter().map(leaf_hash).collect())
+    }
+
+    fn from_leaves(mut leaves: Vec<[u8; 32]>) -> Self {
+        leaves.sort_unstable();
+        leaves.dedup();
+
+        let mut layers = vec![leaves];
+        while layers[layers.len() - 1].len() > 1 {
+            // An odd node is carried up to the next layer as is
+            let layer = layers[layers.len() - 1].chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => node_hash(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            layers.push(layer);
+        }
+        MerkleTree { layers }
+    }
+
+    /// Root to set on the farm, zero for an empty list
+    pub fn root(&self) -> [u8; 32] {
+        self.layers[self.layers.len() - 1].first().copied().unwrap_or_default()
+    }
+
+    /// Proof of a mint, none if the mint isn't in the list
+    pub fn proof(&self, mint: &Pubkey) -> Option<Vec<[u8; 32]>> {
+        self.leaf_proof(&leaf_hash(mint))
+    }
+
+    fn leaf_proof(&self, leaf: &[u8; 32]) -> Option<Vec<[u8; 32]>> {
+        let mut index = self.layers[0].binary_search(leaf).ok()?;
+        let mut proof = vec![];
+        for layer in &self.layers[..self.layers.len() - 1] {
+            if let Some(sibling) = layer.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+        Some(proof)
+    }
+}
+
+pub struct RarityTree {
+    weights: BTreeMap<Pubkey, u64>,
+    tree: MerkleTree,
+}
+
+impl RarityTree {
+    /// Tree of the mints' reward weights, every mint is listed once with a weight the program accepts
+    pub fn new(weights: &[(Pubkey, u64)]) -> Result<Self, RarityError> {
+        let mut table = BTreeMap::new();
+        for (mint, weight) in weights {
+            if !valid_weight(*weight) {
+                return Err(RarityError::WeightOutOfBounds(*mint));
+            }
+            if table.insert(*mint, *weight).is_some() {
+                return Err(RarityError::DuplicateMint(*mint));
+            }
+        }
+        let tree = MerkleTree::from_leaves(table.iter()
+            .map(|(mint, weight)| rarity_leaf_hash(mint, *weight))
+            .collect());
+        Ok(RarityTree { weights: table, tree })
+    }
+
+    /// Root to set on the farm, zero for an empty table
+    pub fn root(&self) -> [u8; 32] {
+        self.tree.root()
+    }
+
+    /// Weight and proof of a mint to stake it with, none if the mint isn't in the table
+    pub fn proof(&self, mint: &Pubkey) -> Option<RarityProof> {
+        let weight_bps = *self.weights.get(mint)?;
+        let proof = self.tree.leaf_proof(&rarity_leaf_hash(mint, weight_bps))?;
+        Some(RarityProof { weight_bps, proof })
+    }
+}
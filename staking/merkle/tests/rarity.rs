@@ -0,0 +1,65 @@
+use anchor_lang::prelude::Pubkey;
+use staking::constants::MAX_RARITY_WEIGHT_BPS;
+use staking::merkle::{verify_proof, verify_rarity_proof};
+use staking_merkle::{parse_rarity_csv, MerkleTree, RarityError, RarityTree};
+
+fn weights(count: usize) -> Vec<(Pubkey, u64)> {
+    (0..count).map(|rank| (Pubkey::new_unique(), 10_000 + 1_000 * rank as u64)).collect()
+}
+
+#[test]
+fn every_weight_has_a_valid_proof() {
+    for count in 1..=17 {
+        let weights = weights(count);
+        let tree = RarityTree::new(&weights).unwrap();
+        for (mint, weight) in &weights {
+            let rarity = tree.proof(mint).unwrap();
+            assert_eq!(rarity.weight_bps, *weight);
+            assert!(verify_rarity_proof(&tree.root(), mint, rarity.weight_bps, &rarity.proof), "{} mints", count);
+        }
+    }
+}
+
+#[test]
+fn proof_only_holds_for_its_weight() {
+    let weights = weights(10);
+    let tree = RarityTree::new(&weights).unwrap();
+    let (mint, weight) = weights[4];
+    let rarity = tree.proof(&mint).unwrap();
+    assert!(!verify_rarity_proof(&tree.root(), &mint, weight + 1, &rarity.proof));
+    assert!(tree.proof(&Pubkey::new_unique()).is_none());
+}
+
+#[test]
+fn rarity_root_doesnt_prove_an_allowlist() {
+    let weights = weights(1);
+    let mints: Vec<Pubkey> = weights.iter().map(|(mint, _)| *mint).collect();
+    assert_ne!(RarityTree::new(&weights).unwrap().root(), MerkleTree::new(&mints).root());
+    assert!(!verify_proof(&RarityTree::new(&weights).unwrap().root(), &mints[0], &[]));
+}
+
+#[test]
+fn rarity_csv_is_parsed_line_by_line() {
+    let weights = weights(2);
+    let text = format!("mint,weight_bps\n{},{}\n\n {} , {} \n", weights[0].0, weights[0].1, weights[1].0, weights[1].1);
+    assert_eq!(parse_rarity_csv(&text).unwrap(), weights);
+
+    let mint = weights[0].0;
+    assert_eq!(parse_rarity_csv(&format!("{}", mint)), Err(RarityError::MissingWeight(1)));
+    assert_eq!(parse_rarity_csv("not a mint,1"), Err(RarityError::InvalidMint(1)));
+    assert_eq!(parse_rarity_csv(&format!("\n{},0", mint)), Err(RarityError::InvalidWeight(2)));
+    assert_eq!(parse_rarity_csv(&format!("{},{}", mint, MAX_RARITY_WEIGHT_BPS + 1)), Err(RarityError::InvalidWeight(1)));
+    assert_eq!(parse_rarity_csv(&format!("{0},1\n{0},2", mint)), Err(RarityError::DuplicateMint(mint)));
+}
+
+#[test]
+fn rarity_table_rejects_duplicates_and_unbounded_weights() {
+    let mint = Pubkey::new_unique();
+    assert_eq!(RarityTree::new(&[(mint, 10_000), (mint, 20_000)]).err(), Some(RarityError::DuplicateMint(mint)));
+    assert_eq!(RarityTree::new(&[(mint, 0)]).err(), Some(RarityError::WeightOutOfBounds(mint)));
+    assert_eq!(
+        RarityTree::new(&[(mint, MAX_RARITY_WEIGHT_BPS + 1)]).err(),
+        Some(RarityError::WeightOutOfBounds(mint)),
+    );
+    assert!(RarityTree::new(&[(mint, MAX_RARITY_WEIGHT_BPS)]).is_ok());
+}
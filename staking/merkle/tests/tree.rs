@@ -0,0 +1,49 @@
+use anchor_lang::prelude::Pubkey;
+use staking::merkle::verify_proof;
+use staking_merkle::{parse_mint_list, MerkleTree};
+
+fn mints(count: usize) -> Vec<Pubkey> {
+    (0..count).map(|_| Pubkey::new_unique()).collect()
+}
+
+#[test]
+fn every_listed_mint_has_a_valid_proof() {
+    for count in 1..=17 {
+        let mints = mints(count);
+        let tree = MerkleTree::new(&mints);
+        for mint in &mints {
+            let proof = tree.proof(mint).unwrap();
+            assert!(verify_proof(&tree.root(), mint, &proof), "{} mints", count);
+        }
+    }
+}
+
+#[test]
+fn unlisted_mints_are_rejected() {
+    let mints = mints(10);
+    let tree = MerkleTree::new(&mints);
+    let unlisted = Pubkey::new_unique();
+    assert!(tree.proof(&unlisted).is_none());
+
+    // A proof only holds for the mint it was built for
+    let proof = tree.proof(&mints[0]).unwrap();
+    assert!(!verify_proof(&tree.root(), &unlisted, &proof));
+    assert!(!verify_proof(&tree.root(), &mints[1], &proof));
+}
+
+#[test]
+fn root_doesnt_depend_on_list_order_or_duplicates() {
+    let mints = mints(8);
+    let mut shuffled: Vec<Pubkey> = mints.iter().rev().copied().collect();
+    shuffled.push(mints[3]);
+    assert_eq!(MerkleTree::new(&mints).root(), MerkleTree::new(&shuffled).root());
+    assert_eq!(MerkleTree::new(&[]).root(), [0; 32]);
+}
+
+#[test]
+fn hashlist_is_parsed_line_by_line() {
+    let mints = mints(3);
+    let text = format!("{}\n\n  {}  \n{}\n", mints[0], mints[1], mints[2]);
+    assert_eq!(parse_mint_list(&text).unwrap(), mints);
+    assert!(parse_mint_list("not a mint").is_err());
+}
@@ -1,7 +1,9 @@
 use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
 
 use crate::constants::*;
 use crate::error::*;
+use crate::reward::*;
 
 #[account]
 #[derive(Default)]
@@ -13,190 +15,627 @@ pub struct GlobalPool {
     pub farm_count: u64,            // 8
 }
 
+/// How a whitelist entry matches an NFT.
+/// The first two kinds read the same as the mint and collection entries stored before the kinds existed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum WhitelistKind {
+    Mint,
+    Collection,         // verified Metaplex collection, or any verified creator
+    UpdateAuthority,    // update authority of the metadata, which has to be a verified creator too
+    Deny,               // blocks the mint even when another entry allows it
+}
+
+impl WhitelistKind {
+    /// Extra seed of an entry's address, entries of different kinds for one address don't collide.
+    /// Allow entries stored before the seeds existed stay at the `[whitelist_address, farm_pool]` address.
+    pub fn seed(&self) -> &'static [u8] {
+        match self {
+            WhitelistKind::Mint => MINT_WHITELIST_SEED.as_bytes(),
+            WhitelistKind::Collection => COLLECTION_WHITELIST_SEED.as_bytes(),
+            WhitelistKind::UpdateAuthority => UPDATE_AUTHORITY_WHITELIST_SEED.as_bytes(),
+            WhitelistKind::Deny => DENYLIST_SEED.as_bytes(),
+        }
+    }
+}
+
+/// Derived from `[ADMIN_PROPOSAL_SEED]`, exists while a super admin handover is pending
 #[account]
 #[derive(Default)]
+pub struct AdminProposal {
+    // 8 + 40
+    pub proposed_admin: Pubkey,     // 32
+    pub proposed_time: i64,         // 8
+}
+
+/// Admin role an operator is granted besides the super admin, who holds every role
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum Role {
+    FarmManager,        // creates, updates and stops farms
+    WhitelistManager,   // manages whitelist entries and merkle allowlists
+    TreasuryManager,    // funds reward vaults and mints reward tokens
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct RoleGrant {
+    pub operator: Pubkey,           // 32
+    pub role: Role,                 // 1
+}
+
+/// Derived from `[ADMIN_ROLES_SEED]`, an operator holding several roles has one grant per role
+#[account]
+#[derive(Default)]
+pub struct AdminRoles {
+    // 8 + 5 + 33 * MAX_ROLE_GRANTS
+    pub bump: u8,                   // 1
+    pub grants: Vec<RoleGrant>,     // 4 + 33 * grant count
+}
+
+impl AdminRoles {
+    pub const LEN: usize = 8 + 1 + 4 + 33 * MAX_ROLE_GRANTS;
+
+    /// Whether `operator` may act as `role`, the super admin always may
+    pub fn allows(
+        &self,
+        super_admin: &Pubkey,
+        operator: &Pubkey,
+        role: Role,
+    ) -> bool {
+        operator == super_admin || self.grants.contains(&RoleGrant { operator: *operator, role })
+    }
+
+    pub fn grant(
+        &mut self,
+        operator: Pubkey,
+        role: Role,
+    ) -> Result<()> {
+        let grant = RoleGrant { operator, role };
+        require!(!self.grants.contains(&grant), StakingError::RoleAlreadyGranted);
+        require!(self.grants.len() < MAX_ROLE_GRANTS, StakingError::TooManyRoleGrants);
+        self.grants.push(grant);
+        Ok(())
+    }
+
+    pub fn revoke(
+        &mut self,
+        operator: Pubkey,
+        role: Role,
+    ) -> Result<()> {
+        let index = self.grants.iter()
+            .position(|grant| *grant == RoleGrant { operator, role })
+            .ok_or(StakingError::RoleNotGranted)?;
+        self.grants.remove(index);
+        Ok(())
+    }
+}
+
+/// Reward weight of an NFT with its proof against the farm's rarity root
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct RarityProof {
+    pub weight_bps: u64,
+    pub proof: Vec<[u8; 32]>,
+}
+
+#[account]
 pub struct WhitelistProof {
-    pub whitelist_address: Pubkey,
-    pub is_collection: bool,
+    // 8 + 41
+    pub whitelist_address: Pubkey,  // 32
+    pub kind: WhitelistKind,        // 1
+    pub multiplier_bps: u64,        // 8, missing from entries stored before the multipliers
+}
+
+impl WhitelistProof {
+    pub const LEN: usize = 8 + 41;
+    const LEGACY_LEN: usize = 8 + 33;
+
+    /// Loads an entry owned by the program, entries stored before the multipliers earn the farm's rate
+    pub fn load(info: &AccountInfo) -> Result<Self> {
+        require!(*info.owner == crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+        let data = info.try_borrow_data()?;
+        if data.len() != Self::LEGACY_LEN {
+            return Self::try_deserialize(&mut &data[..]);
+        }
+        require!(data[..8] == Self::discriminator(), ErrorCode::AccountDiscriminatorMismatch);
+        let (whitelist_address, kind) = <(Pubkey, WhitelistKind)>::deserialize(&mut &data[8..])
+            .map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))?;
+        Ok(WhitelistProof {
+            whitelist_address,
+            kind,
+            multiplier_bps: BPS_DENOMINATOR,
+        })
+    }
+}
+
+/// Tier schedule a farm used until `end_time`
+#[zero_copy]
+#[derive(Default)]
+pub struct RateCheckpoint {
+    pub end_time: i64,
+    pub tier_count: u64,
+    pub tier_duration: [i64; MAX_TIER_COUNT],
+    pub tier_rate: [u64; MAX_TIER_COUNT],
 }
 
+/// The current tier schedule applies since the last rate checkpoint.
+/// Staked NFTs accrue from their `claimed_time` under the schedule of each period,
+/// so an update never changes the reward of time that has already passed.
 #[account(zero_copy)]
+#[derive(Default)]
 pub struct FarmData {
     pub farm_number: u64,
     pub staked_count: u64,
     pub max_stake_count: u64, 
-    pub tier_duration: [i64; 4],
-    pub tier_rate: [u64; 4],
+    pub tier_count: u64,
+    pub tier_duration: [i64; MAX_TIER_COUNT],
+    pub tier_rate: [u64; MAX_TIER_COUNT],
     pub is_stop: bool,
+    pub reward_vault: Pubkey,       // default when the farm mints its rewards
+    pub reward_mint: Pubkey,
+    pub min_lock_duration: i64,
+    pub cooldown_duration: i64,     // time between begin_unstake and finalize_unstake
+    pub penalty_duration: i64,      // unstaking before this duration is penalized
+    pub penalty_bps: u64,           // part of the reward forfeited by an early unstake
+    pub penalty_treasury: Pubkey,   // default when the forfeited reward isn't paid at all
+    pub rate_version: u64,
+    pub rate_history_count: u64,
+    pub rate_history: [RateCheckpoint; MAX_RATE_HISTORY],
+    pub bump: u8,                   // farm is derived from [FARM_SEED, farm_number]
+    pub merkle_root: [u8; 32],      // root of the allowed mints, zero when the farm has none
+    pub rarity_root: [u8; 32],      // root of the mints' reward weights, zero when rewards aren't weighted
+}
+
+impl FarmData {
+    pub fn set_tiers(
+        &mut self,
+        tier_duration: &[i64],
+        tier_rate: &[u64],
+    ) -> Result<()> {
+        validate_tiers(tier_duration, tier_rate)?;
+        let count = tier_rate.len();
+        self.tier_count = count as u64;
+        self.tier_duration = [0; MAX_TIER_COUNT];
+        self.tier_rate = [0; MAX_TIER_COUNT];
+        self.tier_duration[..count].copy_from_slice(tier_duration);
+        self.tier_rate[..count].copy_from_slice(tier_rate);
+        Ok(())
+    }
+
+    /// Replaces the tier schedule from `now` on and keeps the current one as a checkpoint.
+    /// An unchanged schedule takes no checkpoint, and a farm without staked NFTs drops its history
+    /// since no reward accrues under it anymore.
+    /// Otherwise fails once the history is full, dropping a checkpoint would reprice the time it covered.
+    pub fn update_tiers(
+        &mut self,
+        tier_duration: &[i64],
+        tier_rate: &[u64],
+        now: i64,
+    ) -> Result<()> {
+        validate_tiers(tier_duration, tier_rate)?;
+        let tier_count = self.tier_count as usize;
+        if self.tier_duration[..tier_count] == *tier_duration && self.tier_rate[..tier_count] == *tier_rate {
+            return Ok(());
+        }
+
+        if self.staked_count == 0 {
+            self.rate_history = [RateCheckpoint::default(); MAX_RATE_HISTORY];
+            self.rate_history_count = 0;
+        } else {
+            let count = self.rate_history_count as usize;
+            require!(count < MAX_RATE_HISTORY, StakingError::RateHistoryFull);
+            self.rate_history[count] = RateCheckpoint {
+                end_time: now,
+                tier_count: self.tier_count,
+                tier_duration: self.tier_duration,
+                tier_rate: self.tier_rate,
+            };
+            self.rate_history_count = (count + 1) as u64;
+        }
+        self.rate_version += 1;
+
+        self.set_tiers(tier_duration, tier_rate)
+    }
+
+    /// Staked NFTs can't be withdrawn before the lock duration has passed
+    pub fn check_unlocked(
+        &self,
+        staked_time: i64,
+        now: i64,
+    ) -> Result<()> {
+        let unlock_time = staked_time.saturating_add(self.min_lock_duration);
+        if now < unlock_time {
+            msg!("Staked NFT is locked until {}", unlock_time);
+            return Err(error!(StakingError::InvalidWithdrawTime));
+        }
+        Ok(())
+    }
+
+    /// A requested unstake can be finalized once the cooldown has passed
+    pub fn check_cooldown(
+        &self,
+        staked_data: &StakedData,
+        now: i64,
+    ) -> Result<()> {
+        require!(staked_data.unstake_requested_time != 0, StakingError::UnstakeNotRequested);
+        let cooldown_end_time = staked_data.unstake_requested_time.saturating_add(self.cooldown_duration);
+        if now < cooldown_end_time {
+            msg!("Cooldown ends at {}", cooldown_end_time);
+            return Err(error!(StakingError::InvalidWithdrawTime));
+        }
+        Ok(())
+    }
+
+    /// Whether an NFT staked at `staked_time` would still pay the early unstake penalty
+    pub fn in_penalty_period(
+        &self,
+        staked_time: i64,
+        now: i64,
+    ) -> bool {
+        self.penalty_bps > 0 && now < staked_time.saturating_add(self.penalty_duration)
+    }
+
+    /// Part of the reward forfeited by unstaking an NFT staked at `staked_time`
+    pub fn early_unstake_penalty(
+        &self,
+        staked_time: i64,
+        now: i64,
+        reward: u64,
+    ) -> Result<u64> {
+        if !self.in_penalty_period(staked_time, now) {
+            return Ok(0);
+        }
+        let penalty = (reward as u128) * (self.penalty_bps as u128) / (BPS_DENOMINATOR as u128);
+        u64::try_from(penalty).map_err(|_| error!(StakingError::RewardOverflow))
+    }
+
+    pub fn calculate_reward(
+        &self,
+        staked_time: i64,
+        claimed_time: i64,
+        now: i64,
+    ) -> Result<u64> {
+        let mut reward: u64 = 0;
+        let mut start = claimed_time;
+        for checkpoint in self.rate_history[..self.rate_history_count as usize].iter() {
+            if start >= now {
+                return Ok(reward);
+            }
+            if checkpoint.end_time <= start {
+                continue;
+            }
+            let end = checkpoint.end_time.min(now);
+            let count = checkpoint.tier_count as usize;
+            let period_reward = calculate_reward(
+                &checkpoint.tier_duration[..count],
+                &checkpoint.tier_rate[..count],
+                staked_time,
+                start,
+                end,
+            )?;
+            reward = reward.checked_add(period_reward).ok_or(StakingError::RewardOverflow)?;
+            start = end;
+        }
+
+        let count = self.tier_count as usize;
+        let period_reward = calculate_reward(
+            &self.tier_duration[..count],
+            &self.tier_rate[..count],
+            staked_time,
+            start,
+            now,
+        )?;
+        reward.checked_add(period_reward).ok_or_else(|| error!(StakingError::RewardOverflow))
+    }
 }
 
 /// User PDA Layout
-#[zero_copy]
-#[derive(Default, PartialEq)]
-#[repr(packed)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq)]
 pub struct StakedData {
     pub mint: Pubkey,               // 32
+    pub farm: Pubkey,               // 32
     pub farm_number: u64,           // 8
     pub staked_time: i64,           // 8
     pub claimed_time: i64,          // 8
+    pub unstake_requested_time: i64,    // 8, zero until begin_unstake
+    pub multiplier_bps: u64,        // 8, reward multiplier captured at stake time
+    pub rarity_weight_bps: u64,     // 8, rarity weight proven at stake time
 }
 
-#[account(zero_copy)]
-pub struct UserPool {
-    // 8 + 5640
-    pub owner: Pubkey,                              // 32
-    pub staked_count: u64,                          // 8
-    pub staking: [StakedData; STAKE_MAX_COUNT],     // 56 * 100
-}
-
-impl Default for UserPool {
-    #[inline]
-    fn default() -> UserPool {
-        UserPool {
-            owner: Pubkey::default(),
-            staked_count: 0,
-            staking: [
-                StakedData {
-                    ..Default::default()
-                }; STAKE_MAX_COUNT
-            ],
+impl StakedData {
+    pub const LEN: usize = 112;
+
+    /// A stake only accrues and pays under the farm it was staked in
+    pub fn check_farm(&self, farm_pubkey: Pubkey) -> Result<()> {
+        require!(self.farm == farm_pubkey, StakingError::InvalidFarmPool);
+        Ok(())
+    }
+
+    /// Rewards stop accruing once an unstake is requested
+    pub fn accrual_end(&self, now: i64) -> i64 {
+        if self.unstake_requested_time != 0 {
+            now.min(self.unstake_requested_time)
+        } else {
+            now
         }
     }
+
+    pub fn pending_reward(
+        &self,
+        farm: &FarmData,
+        now: i64,
+    ) -> Result<u64> {
+        let reward = farm.calculate_reward(self.staked_time, self.claimed_time, self.accrual_end(now))?;
+        let reward = apply_multiplier(reward, self.multiplier_bps)?;
+        apply_multiplier(reward, self.rarity_weight_bps)
+    }
+
+    /// Reward paid when the NFT is withdrawn. Accrual stops MAX_REWARD_HORIZON after the last claim,
+    /// and a reward the math still can't represent saturates, so the NFT is returned either way.
+    pub fn unstake_reward(
+        &self,
+        farm: &FarmData,
+        now: i64,
+    ) -> u64 {
+        let now = now.min(self.claimed_time.saturating_add(MAX_REWARD_HORIZON));
+        self.pending_reward(farm, now).unwrap_or_else(|err| {
+            msg!("Reward of {} saturates: {}", self.mint, err);
+            u64::MAX
+        })
+    }
+
+    /// Claims are blocked during the penalty period, the unstake then applies the penalty to the whole reward
+    pub fn claim(
+        &mut self,
+        farm: &FarmData,
+        now: i64,
+    ) -> Result<u64> {
+        require!(!farm.in_penalty_period(self.staked_time, self.accrual_end(now)), StakingError::ClaimInPenaltyPeriod);
+        let reward = self.pending_reward(farm, now)?;
+        self.claimed_time = now;
+        Ok(reward)
+    }
+
+    pub fn request_unstake(
+        &mut self,
+        farm: &FarmData,
+        now: i64,
+    ) -> Result<()> {
+        require!(self.unstake_requested_time == 0, StakingError::UnstakeAlreadyRequested);
+        farm.check_unlocked(self.staked_time, now)?;
+        self.unstake_requested_time = now;
+        Ok(())
+    }
+}
+
+/// Stake of one NFT, derived from `[STAKE_RECEIPT_SEED, mint, farm_pool]`.
+/// Unlike the `UserPool` entries, receipts of one owner don't share an account.
+#[account]
+#[derive(Default)]
+pub struct StakeReceipt {
+    // 8 + 145
+    pub owner: Pubkey,              // 32
+    pub bump: u8,                   // 1
+    pub stake: StakedData,          // 112
+}
+
+impl StakeReceipt {
+    pub const LEN: usize = 8 + 32 + 1 + StakedData::LEN;
+}
+
+/// Receipt stakes of one owner in one farm, derived from `[RECEIPT_COUNTER_SEED, farm_pool, owner]`.
+/// With the owner's user pool entries they count against the farm's `max_stake_count`.
+/// The counter is closed when its count drops back to zero and created again by the next receipt stake.
+/// Every receipt stake writes it, so the owner's receipt stakes in the farm can't land in parallel,
+/// only the receipts themselves are independent accounts.
+#[account]
+#[derive(Default)]
+pub struct ReceiptCounter {
+    // 8 + 9
+    pub bump: u8,                   // 1
+    pub count: u64,                 // 8
+}
+
+impl ReceiptCounter {
+    pub const LEN: usize = 8 + 1 + 8;
+}
+
+/// Derived from `[USER_POOL_SEED, owner]`, the account grows as more NFTs are staked
+#[account]
+#[derive(Default)]
+pub struct UserPool {
+    // 8 + 37 + 112 * staked count
+    pub owner: Pubkey,                  // 32
+    pub bump: u8,                       // 1
+    pub staking: Vec<StakedData>,       // 4 + 112 * staked count
 }
 
 impl UserPool {
+    /// Account size holding `staked_count` staked NFTs
+    pub fn space(staked_count: usize) -> usize {
+        8 + 32 + 1 + 4 + StakedData::LEN * staked_count
+    }
+
+    /// Staked NFT of the pool, which has to be staked in the given farm
+    pub fn staked_nft_mut(
+        &mut self,
+        nft_pubkey: Pubkey,
+        farm_pubkey: Pubkey,
+    ) -> Result<&mut StakedData> {
+        let staked_data = self.staking.iter_mut()
+            .find(|staked_data| staked_data.mint == nft_pubkey)
+            .ok_or(StakingError::InvalidNFTAddress)?;
+        staked_data.check_farm(farm_pubkey)?;
+        Ok(staked_data)
+    }
+
     pub fn add_nft(
         &mut self,
         nft_pubkey: Pubkey,
+        farm_pubkey: Pubkey,
         farm_number: u64,
+        multiplier_bps: u64,
+        rarity_weight_bps: u64,
         now: i64,
-    ) {
-        let idx = self.staked_count as usize;
-        self.staking[idx].mint = nft_pubkey;
-        self.staking[idx].farm_number = farm_number;
-        self.staking[idx].staked_time = now;
-        self.staking[idx].claimed_time = now;
-        self.staked_count += 1;
-    }
-    
+    ) -> Result<()> {
+        require!(self.staking.len() < STAKE_MAX_COUNT, StakingError::ExceedMaxCount);
+        self.staking.push(StakedData {
+            mint: nft_pubkey,
+            farm: farm_pubkey,
+            farm_number,
+            staked_time: now,
+            claimed_time: now,
+            unstake_requested_time: 0,
+            multiplier_bps,
+            rarity_weight_bps,
+        });
+        Ok(())
+    }
+
     pub fn remove_nft(
         &mut self,
         nft_pubkey: Pubkey,
-        tier_duration: [i64; 4],
-        tier_rate: [u64; 4],
+        farm_pubkey: Pubkey,
+        farm: &FarmData,
         now: i64,
-    ) -> Result<u64> {
-        let mut withdrawn: u8 = 0;
-        let mut index: usize = 0;
-        let mut total_reward: u64 = 0;
+    ) -> Result<(StakedData, u64)> {
         // Find NFT in pool
-        for i in 0..self.staked_count {
-            let idx = i as usize;
-            if self.staking[idx].mint.eq(&nft_pubkey) {
-                let mut duration: i64 = now - self.staking[idx].staked_time;
-                let mut reward: u64 = 0;
-                for j in 0..3 {
-                    if duration >= tier_duration[j] {
-                        reward += (tier_duration[j] as u64) * tier_rate[j];
-                        duration -= tier_duration[j];
-
-                    } else {
-                        reward += (duration as u64) * tier_rate[j];
-                        duration = 0;
-                        break;
-                    }
-                }
-                if duration != 0 {
-                    reward += (duration as u64) * tier_rate[3];
-                }
+        let index = self.staking.iter()
+            .position(|staked_data| staked_data.mint == nft_pubkey)
+            .ok_or(StakingError::InvalidNFTAddress)?;
+        let staked_data = self.staking[index];
+        staked_data.check_farm(farm_pubkey)?;
+        farm.check_unlocked(staked_data.staked_time, now)?;
+        let reward = staked_data.unstake_reward(farm, now);
 
-                let mut duration: i64 = self.staking[idx].claimed_time - self.staking[idx].staked_time;
-                let mut virtual_reward: u64 = 0;
-                for j in 0..3 {
-                    if duration >= tier_duration[j] {
-                        virtual_reward += (tier_duration[j] as u64) * tier_rate[j];
-                        duration -= tier_duration[j];
-
-                    } else {
-                        virtual_reward += (duration as u64) * tier_rate[j];
-                        duration = 0;
-                        break;
-                    }
-                }
-                if duration != 0 {
-                    virtual_reward += (duration as u64) * tier_rate[3];
-                }
+        // Remove NFT from pool
+        self.staking.swap_remove(index);
+        Ok((staked_data, reward))
+    }
 
-                total_reward = reward - virtual_reward;
+    pub fn request_unstake(
+        &mut self,
+        nft_pubkey: Pubkey,
+        farm_pubkey: Pubkey,
+        farm: &FarmData,
+        now: i64,
+    ) -> Result<()> {
+        self.staked_nft_mut(nft_pubkey, farm_pubkey)?.request_unstake(farm, now)
+    }
 
-                index = idx;
-                withdrawn = 1;
-                break;
-            }
-        }
-        require!(withdrawn == 1, StakingError::InvalidNFTAddress);
-       
-        // Remove NFT from pool
-        let last_idx: usize = (self.staked_count - 1) as usize;
-        if index != last_idx {
-            self.staking[index] = self.staking[last_idx];
-        }
-        self.staked_count -= 1;
-        Ok(total_reward)
+    /// Removes a staked NFT without settling its reward, its entry moves as is
+    pub fn take_nft(
+        &mut self,
+        nft_pubkey: Pubkey,
+        farm_pubkey: Pubkey,
+    ) -> Result<StakedData> {
+        let index = self.staking.iter()
+            .position(|staked_data| staked_data.mint == nft_pubkey)
+            .ok_or(StakingError::InvalidNFTAddress)?;
+        self.staking[index].check_farm(farm_pubkey)?;
+        Ok(self.staking.swap_remove(index))
     }
 
     pub fn claim_reward(
         &mut self,
         nft_pubkey: Pubkey,
-        tier_duration: [i64; 4],
-        tier_rate: [u64; 4],
+        farm_pubkey: Pubkey,
+        farm: &FarmData,
+        now: i64,
+    ) -> Result<u64> {
+        self.staked_nft_mut(nft_pubkey, farm_pubkey)?.claim(farm, now)
+    }
+
+    pub fn claim_farm_reward(
+        &mut self,
+        farm_pubkey: Pubkey,
+        farm: &FarmData,
         now: i64,
     ) -> Result<u64> {
         let mut total_reward: u64 = 0;
-        // Find NFT in pool
-        for i in 0..self.staked_count {
-            let idx = i as usize;
-            if self.staking[idx].mint.eq(&nft_pubkey) {
-                let mut duration: i64 = now - self.staking[idx].staked_time;
-                let mut reward: u64 = 0;
-                for j in 0..3 {
-                    if duration >= tier_duration[j] {
-                        reward += (tier_duration[j] as u64) * tier_rate[j];
-                        duration -= tier_duration[j];
-
-                    } else {
-                        reward += (duration as u64) * tier_rate[j];
-                        duration = 0;
-                        break;
-                    }
-                }
-                if duration != 0 {
-                    reward += (duration as u64) * tier_rate[3];
-                }
+        // NFTs still in the penalty period are left to accrue
+        for staked_data in self.staking.iter_mut().filter(|staked_data| {
+            staked_data.farm == farm_pubkey
+                && !farm.in_penalty_period(staked_data.staked_time, staked_data.accrual_end(now))
+        }) {
+            let reward = staked_data.claim(farm, now)?;
+            total_reward = total_reward.checked_add(reward).ok_or(StakingError::RewardOverflow)?;
+        }
+        Ok(total_reward)
+    }
+}
 
-                let mut duration: i64 = self.staking[idx].claimed_time - self.staking[idx].staked_time;
-                let mut virtual_reward: u64 = 0;
-                for j in 0..3 {
-                    if duration >= tier_duration[j] {
-                        virtual_reward += (tier_duration[j] as u64) * tier_rate[j];
-                        duration -= tier_duration[j];
-
-                    } else {
-                        virtual_reward += (duration as u64) * tier_rate[j];
-                        duration = 0;
-                        break;
-                    }
-                }
-                if duration != 0 {
-                    virtual_reward += (duration as u64) * tier_rate[3];
-                }
+/// Stake in a legacy user pool, packed in 56 bytes
+#[derive(Clone, Copy, Default, PartialEq)]
+pub struct LegacyStakedData {
+    pub mint: Pubkey,               // 32
+    pub farm_number: u64,           // 8
+    pub staked_time: i64,           // 8
+    pub claimed_time: i64,          // 8
+}
 
-                total_reward = reward - virtual_reward;
+/// User pool of the first program version, a zero-copy account created by the client at
+/// `create_with_seed(owner, LEGACY_USER_POOL_SEED, program_id)` with room for `STAKE_MAX_COUNT` stakes.
+/// It shares the `UserPool` discriminator, so it's told apart by its size.
+pub struct LegacyUserPool {
+    pub owner: Pubkey,
+    pub staking: Vec<LegacyStakedData>,
+}
 
-                self.staking[idx].claimed_time = now;
-                break;
-            }
+impl LegacyUserPool {
+    pub const STAKED_DATA_LEN: usize = 56;
+    // 8 + 5640
+    pub const LEN: usize = 8 + 32 + 8 + Self::STAKED_DATA_LEN * STAKE_MAX_COUNT;
+
+    pub fn address(owner: &Pubkey) -> Result<Pubkey> {
+        Pubkey::create_with_seed(owner, LEGACY_USER_POOL_SEED, &crate::ID)
+            .map_err(|_| error!(StakingError::InvalidUserPool))
+    }
+
+    pub fn load(info: &AccountInfo) -> Result<Self> {
+        require!(*info.owner == crate::ID, StakingError::InvalidUserPool);
+        let data = info.try_borrow_data()?;
+        require!(data.len() == Self::LEN && data[..8] == UserPool::discriminator(), StakingError::InvalidUserPool);
+
+        let read_u64 = |offset: usize| u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+        let staked_count = read_u64(40) as usize;
+        require!(staked_count <= STAKE_MAX_COUNT, StakingError::InvalidUserPool);
+        let staking = (0..staked_count)
+            .map(|i| {
+                let offset = 48 + Self::STAKED_DATA_LEN * i;
+                LegacyStakedData {
+                    mint: Pubkey::new(&data[offset..offset + 32]),
+                    farm_number: read_u64(offset + 32),
+                    staked_time: read_u64(offset + 40) as i64,
+                    claimed_time: read_u64(offset + 48) as i64,
+                }
+            })
+            .collect();
+        Ok(LegacyUserPool {
+            owner: Pubkey::new(&data[8..40]),
+            staking,
+        })
+    }
+
+    /// Writes the stakes back in the legacy layout
+    pub fn store(&self, info: &AccountInfo) -> Result<()> {
+        let mut data = info.try_borrow_mut_data()?;
+        data[40..48].copy_from_slice(&(self.staking.len() as u64).to_le_bytes());
+        data[48..].fill(0);
+        for (i, staked_data) in self.staking.iter().enumerate() {
+            let offset = 48 + Self::STAKED_DATA_LEN * i;
+            data[offset..offset + 32].copy_from_slice(staked_data.mint.as_ref());
+            data[offset + 32..offset + 40].copy_from_slice(&staked_data.farm_number.to_le_bytes());
+            data[offset + 40..offset + 48].copy_from_slice(&staked_data.staked_time.to_le_bytes());
+            data[offset + 48..offset + 56].copy_from_slice(&staked_data.claimed_time.to_le_bytes());
         }
-        Ok(total_reward)
+        Ok(())
     }
-}
\ No newline at end of file
+
+    pub fn remove_nft(
+        &mut self,
+        nft_pubkey: Pubkey,
+    ) -> Result<LegacyStakedData> {
+        let index = self.staking.iter()
+            .position(|staked_data| staked_data.mint == nft_pubkey)
+            .ok_or(StakingError::InvalidNFTAddress)?;
+        Ok(self.staking.swap_remove(index))
+    }
+}
@@ -1,3 +1,29 @@
 pub const GLOBAL_AUTHORITY_SEED: &str = "global-authority";
+pub const REWARD_VAULT_SEED: &str = "reward-vault";
+pub const USER_POOL_SEED: &str = "user-pool";
+// seed of the legacy user pools, which aren't PDAs but derived with `create_with_seed`
+pub const LEGACY_USER_POOL_SEED: &str = "user-pool";
+pub const STAKE_RECEIPT_SEED: &str = "stake-receipt";
+pub const RECEIPT_COUNTER_SEED: &str = "receipt-counter";
+pub const FARM_SEED: &str = "farm";
+pub const MINT_WHITELIST_SEED: &str = "mint";
+pub const COLLECTION_WHITELIST_SEED: &str = "collection";
+pub const UPDATE_AUTHORITY_WHITELIST_SEED: &str = "update-authority";
+pub const DENYLIST_SEED: &str = "denylist";
+pub const ADMIN_ROLES_SEED: &str = "admin-roles";
+pub const ADMIN_PROPOSAL_SEED: &str = "admin-proposal";
 
-pub const STAKE_MAX_COUNT: usize = 100;
\ No newline at end of file
+pub const STAKE_MAX_COUNT: usize = 100;
+pub const MAX_TIER_COUNT: usize = 8;
+pub const MAX_RATE_HISTORY: usize = 8;
+pub const MAX_ROLE_GRANTS: usize = 16;
+pub const BPS_DENOMINATOR: u64 = 10_000;
+// 10x the farm's rate
+pub const MAX_REWARD_MULTIPLIER_BPS: u64 = 100_000;
+// 10x the farm's rate
+pub const MAX_RARITY_WEIGHT_BPS: u64 = 100_000;
+// 0.5 tokens per second with 9 decimals, with the max multiplier and weight
+// the reward of one stake fits in u64 for MAX_REWARD_HORIZON
+pub const MAX_TIER_RATE: u64 = 500_000_000;
+// 10 years
+pub const MAX_REWARD_HORIZON: i64 = 10 * 365 * 24 * 60 * 60;
\ No newline at end of file
@@ -33,4 +33,60 @@ pub enum StakingError {
     MetadataCreatorParseError,
     #[msg("Unknown Collection Or The Collection Is Not Allowed")]
     UnkownOrNotAllowedNFTCollection,
+    #[msg("Reward Calculation Overflow")]
+    RewardOverflow,
+    #[msg("Farm Account Of A Staked NFT Is Not Provided")]
+    MissingFarmAccount,
+    #[msg("Invalid Reward Vault Address")]
+    InvalidRewardVault,
+    #[msg("Invalid Reward Mint Of The Farm")]
+    InvalidRewardMint,
+    #[msg("Tier Count Must Be Between 1 And The Max Tier Count")]
+    InvalidTierCount,
+    #[msg("Tier Durations And Rates Have Different Lengths")]
+    TierLengthMismatch,
+    #[msg("Tier Durations Must Be Positive")]
+    InvalidTierDuration,
+    #[msg("Tier Rate Exceeds The Max Rate")]
+    TierRateTooHigh,
+    #[msg("Invalid Penalty Treasury Address")]
+    InvalidPenaltyTreasury,
+    #[msg("This Farm Requires Begin Unstake And A Cooldown")]
+    CooldownRequired,
+    #[msg("Unstake Is Already Requested")]
+    UnstakeAlreadyRequested,
+    #[msg("Unstake Is Not Requested")]
+    UnstakeNotRequested,
+    #[msg("Staked NFT Doesn't Belong To This Farm")]
+    InvalidFarmPool,
+    #[msg("User Pool Still Has Staked NFTs")]
+    UserPoolNotEmpty,
+    #[msg("NFT Has No Verified Collection Or Creator")]
+    UnverifiedCollection,
+    #[msg("Invalid Merkle Proof Of The NFT")]
+    InvalidMerkleProof,
+    #[msg("NFT Is Denied In This Farm")]
+    DeniedNFT,
+    #[msg("Reward Multiplier Must Be Between 1 And The Max Multiplier")]
+    InvalidRewardMultiplier,
+    #[msg("Invalid Rarity Proof Of The NFT")]
+    InvalidRarityProof,
+    #[msg("Signer Doesn't Have The Required Role")]
+    MissingRole,
+    #[msg("Role Is Already Granted To The Operator")]
+    RoleAlreadyGranted,
+    #[msg("Role Isn't Granted To The Operator")]
+    RoleNotGranted,
+    #[msg("Exceed Max Role Grant Count")]
+    TooManyRoleGrants,
+    #[msg("Signer Isn't The Proposed Admin")]
+    InvalidProposedAdmin,
+    #[msg("Farm Rate History Is Full")]
+    RateHistoryFull,
+    #[msg("Reward Can't Be Claimed During The Early Unstake Penalty Period")]
+    ClaimInPenaltyPeriod,
+    #[msg("Rarity Weight Must Be Between 1 And The Max Weight")]
+    InvalidRarityWeight,
+    #[msg("Deny Entry Address Of The NFT Is Missing")]
+    MissingDenyAccount,
 }
\ No newline at end of file
@@ -0,0 +1,225 @@
+use anchor_lang::prelude::*;
+
+use crate::account::{Role, WhitelistKind};
+
+#[event]
+pub struct InitializeEvent {
+    pub super_admin: Pubkey,
+    pub reward_token: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposeAdminEvent {
+    pub super_admin: Pubkey,
+    pub proposed_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CancelAdminProposalEvent {
+    pub super_admin: Pubkey,
+    pub proposed_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AcceptAdminEvent {
+    pub previous_admin: Pubkey,
+    pub new_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GrantRoleEvent {
+    pub operator: Pubkey,
+    pub role: Role,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RevokeRoleEvent {
+    pub operator: Pubkey,
+    pub role: Role,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CloseUserPoolEvent {
+    pub owner: Pubkey,
+    pub user_pool: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CreateFarmEvent {
+    pub farm_number: u64,
+    pub farm: Pubkey,
+    pub reward_mint: Pubkey,
+    pub tier_duration: Vec<i64>,
+    pub tier_rate: Vec<u64>,
+    pub max_stake_count: u64,
+    pub min_lock_duration: i64,
+    pub cooldown_duration: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UpdateFarmEvent {
+    pub farm_number: u64,
+    pub farm: Pubkey,
+    pub tier_duration: Vec<i64>,
+    pub tier_rate: Vec<u64>,
+    pub max_stake_count: u64,
+    pub min_lock_duration: i64,
+    pub cooldown_duration: i64,
+    pub rate_version: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AddWhitelistEvent {
+    pub farm_number: u64,
+    pub whitelist_address: Pubkey,
+    pub kind: WhitelistKind,
+    pub multiplier_bps: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RemoveWhitelistEvent {
+    pub farm_number: u64,
+    pub whitelist_address: Pubkey,
+    pub kind: WhitelistKind,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SetStopEvent {
+    pub farm_number: u64,
+    pub is_stop: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SetMerkleRootEvent {
+    pub farm_number: u64,
+    pub merkle_root: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SetRarityRootEvent {
+    pub farm_number: u64,
+    pub rarity_root: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakeEvent {
+    pub farm_number: u64,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub multiplier_bps: u64,
+    pub rarity_weight_bps: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BeginUnstakeEvent {
+    pub farm_number: u64,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub cooldown_end_time: i64,
+    pub timestamp: i64,
+}
+
+/// `reward` is the accrued reward, `penalty` the part of it forfeited by an early unstake.
+/// `unpaid_reward` is the rest of the reward when the farm's vault couldn't pay it, or all of it when the farm is stopped.
+#[event]
+pub struct UnstakeEvent {
+    pub farm_number: u64,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub reward: u64,
+    pub penalty: u64,
+    pub unpaid_reward: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UnstakeLegacyNftEvent {
+    pub farm_number: u64,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ClaimEvent {
+    pub farm_number: u64,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub reward: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MigrateToReceiptEvent {
+    pub farm_number: u64,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub stake_receipt: Pubkey,
+    pub timestamp: i64,
+}
+
+/// `farm_number` is the filter of the claim, none if every farm was claimed
+#[event]
+pub struct ClaimAllEvent {
+    pub farm_number: Option<u64>,
+    pub owner: Pubkey,
+    pub reward: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SetUnstakePenaltyEvent {
+    pub farm_number: u64,
+    pub penalty_duration: i64,
+    pub penalty_bps: u64,
+    pub penalty_treasury: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InitRewardVaultEvent {
+    pub farm_number: u64,
+    pub reward_vault: Pubkey,
+    pub reward_mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FundRewardVaultEvent {
+    pub farm_number: u64,
+    pub reward_vault: Pubkey,
+    pub admin: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TransferMintAuthorityEvent {
+    pub reward_mint: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MintToAccountEvent {
+    pub reward_mint: Pubkey,
+    pub user_reward_account: Pubkey,
+    pub admin: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
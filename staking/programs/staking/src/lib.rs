@@ -1,18 +1,24 @@
 use anchor_lang::{
     prelude::*,
+    AccountsClose,
+    system_program,
 };
 use solana_program::program::{invoke_signed};
 use anchor_spl::{
     token::{self, Mint, Token, TokenAccount, Transfer, MintTo, SetAuthority },
 };
-use metaplex_token_metadata::state::Metadata;
+use mpl_token_metadata::state::{Metadata, TokenMetadataAccount};
 
 pub mod account;
 pub mod error;
 pub mod constants;
+pub mod event;
+pub mod reward;
+pub mod merkle;
 
 use account::*;
 use error::*;
+use event::*;
 use constants::*;
 
 declare_id!("3nJ33QN3CnVuqUVJc9URnRDVbSMSSp14sb45p4PVUyE9");
@@ -25,14 +31,121 @@ pub mod staking {
         let global_authority = &mut ctx.accounts.global_authority;
         global_authority.super_admin = ctx.accounts.admin.key();
         global_authority.reward_token = ctx.accounts.reward_token.key();
+
+        emit!(InitializeEvent {
+            super_admin: global_authority.super_admin,
+            reward_token: global_authority.reward_token,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Starts handing the super admin over to `new_admin`, who has to accept it.
+    /// A pending proposal has to be cancelled before proposing another admin.
+    pub fn propose_admin(
+        ctx: Context<ProposeAdmin>,
+        new_admin: Pubkey,
+    ) -> Result<()> {
+        let timestamp = Clock::get()?.unix_timestamp;
+        let admin_proposal = &mut ctx.accounts.admin_proposal;
+        admin_proposal.proposed_admin = new_admin;
+        admin_proposal.proposed_time = timestamp;
+
+        emit!(ProposeAdminEvent {
+            super_admin: ctx.accounts.admin.key(),
+            proposed_admin: new_admin,
+            timestamp,
+        });
+        Ok(())
+    }
+
+    /// Drops the pending proposal and refunds its rent to the super admin
+    pub fn cancel_admin_proposal(
+        ctx: Context<CancelAdminProposal>,
+    ) -> Result<()> {
+        emit!(CancelAdminProposalEvent {
+            super_admin: ctx.accounts.admin.key(),
+            proposed_admin: ctx.accounts.admin_proposal.proposed_admin,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Completes the handover, signed by the proposed admin who receives the proposal's rent
+    pub fn accept_admin(
+        ctx: Context<AcceptAdmin>,
+    ) -> Result<()> {
+        let global_authority = &mut ctx.accounts.global_authority;
+        let previous_admin = global_authority.super_admin;
+        global_authority.super_admin = ctx.accounts.new_admin.key();
+
+        emit!(AcceptAdminEvent {
+            previous_admin,
+            new_admin: global_authority.super_admin,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Creates the account of the admin roles, once after `initialize`
+    pub fn init_admin_roles(
+        ctx: Context<InitAdminRoles>,
+    ) -> Result<()> {
+        ctx.accounts.admin_roles.bump = *ctx.bumps.get("admin_roles").unwrap();
+        Ok(())
+    }
+
+    pub fn grant_role(
+        ctx: Context<GrantRole>,
+        operator: Pubkey,
+        role: Role,
+    ) -> Result<()> {
+        ctx.accounts.admin_roles.grant(operator, role)?;
+
+        emit!(GrantRoleEvent {
+            operator,
+            role,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn revoke_role(
+        ctx: Context<RevokeRole>,
+        operator: Pubkey,
+        role: Role,
+    ) -> Result<()> {
+        ctx.accounts.admin_roles.revoke(operator, role)?;
+
+        emit!(RevokeRoleEvent {
+            operator,
+            role,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         Ok(())
     }
 
     pub fn initialize_user_pool(
         ctx: Context<InitializeUserPool>
     ) -> Result<()> {
-        let mut user_pool = ctx.accounts.user_pool.load_init()?;
+        let user_pool = &mut ctx.accounts.user_pool;
         user_pool.owner = ctx.accounts.owner.key();
+        user_pool.bump = *ctx.bumps.get("user_pool").unwrap();
+        Ok(())
+    }
+
+    /// Closes a user pool without staked NFTs and refunds its rent to the owner
+    #[access_control(user(&ctx.accounts.user_pool, &ctx.accounts.owner))]
+    pub fn close_user_pool(
+        ctx: Context<CloseUserPool>
+    ) -> Result<()> {
+        require!(ctx.accounts.user_pool.staking.is_empty(), StakingError::UserPoolNotEmpty);
+
+        emit!(CloseUserPoolEvent {
+            owner: ctx.accounts.owner.key(),
+            user_pool: ctx.accounts.user_pool.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         Ok(())
     }
 
@@ -41,24 +154,37 @@ pub mod staking {
         tier_duration: Vec<i64>,
         tier_rate: Vec<u64>,
         max_stake_count: u64,
+        min_lock_duration: i64,
+        cooldown_duration: i64,
         count: u64
     ) -> Result<()> {
         let global_authority = &mut ctx.accounts.global_authority;
         let mut farm_pool = ctx.accounts.farm_pool.load_init()?;
 
-        // rquuire!(ctx.accounts.admin.key == ADMIN.parse::<Pubkey>().unwrap(), );
         require!(global_authority.farm_count + 1 == count, StakingError::InvalidFarmCount);
-        require!(tier_duration.len() == tier_rate.len(), StakingError::InvalidInput);
+        require!(min_lock_duration >= 0 && cooldown_duration >= 0, StakingError::InvalidInput);
         farm_pool.farm_number = count;
+        farm_pool.bump = *ctx.bumps.get("farm_pool").unwrap();
         farm_pool.max_stake_count = max_stake_count;
-        for i in 0..4 {
-            msg!("Tier Duration {:?} and Rate {:?}", tier_duration[i as usize], tier_rate[i as usize]);
-            farm_pool.tier_duration[i as usize] = tier_duration[i as usize];
-            farm_pool.tier_rate[i as usize] = tier_rate[i as usize];
-        }
+        farm_pool.min_lock_duration = min_lock_duration;
+        farm_pool.cooldown_duration = cooldown_duration;
+        farm_pool.reward_mint = ctx.accounts.reward_mint.key();
+        msg!("Tier Duration {:?} and Rate {:?}", tier_duration, tier_rate);
+        farm_pool.set_tiers(&tier_duration, &tier_rate)?;
 
         global_authority.farm_count += 1;
 
+        emit!(CreateFarmEvent {
+            farm_number: count,
+            farm: ctx.accounts.farm_pool.key(),
+            reward_mint: ctx.accounts.reward_mint.key(),
+            tier_duration,
+            tier_rate,
+            max_stake_count,
+            min_lock_duration,
+            cooldown_duration,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         Ok(())
     }
 
@@ -67,53 +193,86 @@ pub mod staking {
         new_tier_duration: Vec<i64>,
         new_tier_rate: Vec<u64>,
         max_stake_count: u64,
+        min_lock_duration: i64,
+        cooldown_duration: i64,
     ) -> Result<()> {
         let mut farm_pool = ctx.accounts.farm_pool.load_mut()?;
-        let global_authority = &mut ctx.accounts.global_authority;
-
-        require!(global_authority.super_admin.key() == ctx.accounts.admin.key(), 
-            StakingError::InvalidSuperOwner);
+        require!(min_lock_duration >= 0 && cooldown_duration >= 0, StakingError::InvalidInput);
 
-        for i in 0..4 {
-            farm_pool.tier_duration[i] = new_tier_duration[i];
-            farm_pool.tier_rate[i] = new_tier_rate[i];
-        }
+        let timestamp = Clock::get()?.unix_timestamp;
+        farm_pool.update_tiers(&new_tier_duration, &new_tier_rate, timestamp)?;
+        msg!("Farm {} Rate Version: {}", farm_pool.farm_number, farm_pool.rate_version);
 
         farm_pool.max_stake_count = max_stake_count;
-
+        farm_pool.min_lock_duration = min_lock_duration;
+        farm_pool.cooldown_duration = cooldown_duration;
+
+        emit!(UpdateFarmEvent {
+            farm_number: farm_pool.farm_number,
+            farm: ctx.accounts.farm_pool.key(),
+            tier_duration: new_tier_duration,
+            tier_rate: new_tier_rate,
+            max_stake_count,
+            min_lock_duration,
+            cooldown_duration,
+            rate_version: farm_pool.rate_version,
+            timestamp,
+        });
         Ok(())
     }
 
+    /// Adds a whitelist entry of `kind` for `whitelist_address`, which is a mint for mint and deny entries.
+    /// NFTs staked through an allow entry earn `multiplier_bps` of the farm's rate, deny entries ignore it.
     pub fn add_whitelist(
         ctx: Context<AddWhitelist>,
-        is_collection: bool
+        kind: WhitelistKind,
+        multiplier_bps: u64,
     ) -> Result<()> {
-        let global_authority = &mut ctx.accounts.global_authority;
-        if global_authority.super_admin != ctx.accounts.admin.key() {
-            return Err(error!(StakingError::InvalidSuperOwner));
-        }
+        require!(multiplier_bps > 0 && multiplier_bps <= MAX_REWARD_MULTIPLIER_BPS,
+            StakingError::InvalidRewardMultiplier);
         let whitelist_proof = &mut ctx.accounts.whitelist_proof;
 
         whitelist_proof.whitelist_address = ctx.accounts.whitelist_address.key();
-        whitelist_proof.is_collection = is_collection;
+        whitelist_proof.kind = kind;
+        whitelist_proof.multiplier_bps = multiplier_bps;
+
+        emit!(AddWhitelistEvent {
+            farm_number: ctx.accounts.farm_pool.load()?.farm_number,
+            whitelist_address: whitelist_proof.whitelist_address,
+            kind,
+            multiplier_bps,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         Ok(())
     }
 
+    /// Removes a whitelist entry, including allow entries stored at the address they had before the kind seeds
     pub fn remove_whitelist(
         ctx: Context<RemoveWhitelist>,
     ) -> Result<()> {
-        let global_authority = &mut ctx.accounts.global_authority;
-        if global_authority.super_admin != ctx.accounts.admin.key() {
-            return Err(error!(StakingError::InvalidSuperOwner));
-        }
-        let whitelist_proof = &mut ctx.accounts.whitelist_proof;
-        require!(!whitelist_proof.to_account_info().data_is_empty(), StakingError::InvalidWhitelistAddress);
+        let whitelist_proof = &ctx.accounts.whitelist_proof;
+        require!(!whitelist_proof.data_is_empty(), StakingError::InvalidWhitelistAddress);
+        let entry = WhitelistProof::load(whitelist_proof)?;
+        let farm_pool = ctx.accounts.farm_pool.key();
+        require!(entry.whitelist_address == ctx.accounts.whitelist_address.key(), StakingError::InvalidWhitelistAddress);
+        require!(
+            whitelist_proof.key() == whitelist_address(&entry.whitelist_address, &farm_pool, entry.kind)
+                || (entry.kind != WhitelistKind::Deny
+                    && whitelist_proof.key() == legacy_whitelist_address(&entry.whitelist_address, &farm_pool)),
+            StakingError::InvalidWhitelistAddress
+        );
 
         let admin = &mut ctx.accounts.admin;
         let starting_lamports: u64 = admin.lamports();
-        **admin.lamports.borrow_mut() = starting_lamports + whitelist_proof.to_account_info().lamports();
-        **whitelist_proof.to_account_info().lamports.borrow_mut() = 0;
-        
+        **admin.lamports.borrow_mut() = starting_lamports + whitelist_proof.lamports();
+        **whitelist_proof.lamports.borrow_mut() = 0;
+
+        emit!(RemoveWhitelistEvent {
+            farm_number: ctx.accounts.farm_pool.load()?.farm_number,
+            whitelist_address: ctx.accounts.whitelist_address.key(),
+            kind: entry.kind,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         Ok(())
     }
 
@@ -121,89 +280,81 @@ pub mod staking {
         ctx: Context<SetStop>,
         is_stop: bool
     ) -> Result<()> {
-        let global_authority = &mut ctx.accounts.global_authority;
-        if global_authority.super_admin != ctx.accounts.admin.key() {
-            return Err(error!(StakingError::InvalidSuperOwner));
-        }
         let mut farm_pool = ctx.accounts.farm_pool.load_mut()?;
         farm_pool.is_stop = is_stop;
+
+        emit!(SetStopEvent {
+            farm_number: farm_pool.farm_number,
+            is_stop,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Sets the merkle root of the mints allowed in the farm, a zero root disables it
+    pub fn set_merkle_root(
+        ctx: Context<SetMerkleRoot>,
+        merkle_root: [u8; 32],
+    ) -> Result<()> {
+        let mut farm_pool = ctx.accounts.farm_pool.load_mut()?;
+        farm_pool.merkle_root = merkle_root;
+
+        emit!(SetMerkleRootEvent {
+            farm_number: farm_pool.farm_number,
+            merkle_root,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Sets the merkle root of the mints' reward weights in the farm, a zero root stops weighting new stakes
+    pub fn set_rarity_root(
+        ctx: Context<SetRarityRoot>,
+        rarity_root: [u8; 32],
+    ) -> Result<()> {
+        let mut farm_pool = ctx.accounts.farm_pool.load_mut()?;
+        farm_pool.rarity_root = rarity_root;
+
+        emit!(SetRarityRootEvent {
+            farm_number: farm_pool.farm_number,
+            rarity_root,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         Ok(())
     }
 
+    /// Stakes a whitelisted NFT. `merkle_proof` proves the mint against the farm's merkle root,
+    /// it's none when a whitelist proof account is passed instead.
+    /// `rarity` proves the NFT's reward weight when the farm has a rarity root.
     #[access_control(user(&ctx.accounts.user_pool, &ctx.accounts.owner))]
     pub fn stake_nft_to_pool(
         ctx: Context<StakeNftToPool>,
         _global_bump: u8,
+        merkle_proof: Option<Vec<[u8; 32]>>,
+        rarity: Option<RarityProof>,
     ) -> Result<()> {
         let mut farm_pool = ctx.accounts.farm_pool.load_mut()?;
-        let mut user_pool = ctx.accounts.user_pool.load_mut()?;
-        let mut staked_farm_count: u64 = 0;
-        for i in 0..user_pool.staked_count {
-            if user_pool.staking[i as usize].farm_number == farm_pool.farm_number {
-                staked_farm_count +=1;
-            }
-        }
+        let user_pool = &mut ctx.accounts.user_pool;
+        let receipt_count = load_initialized::<ReceiptCounter>(&ctx.accounts.receipt_counter)?
+            .map_or(0, |receipt_counter| receipt_counter.count);
+        let staked_farm_count = farm_stake_count(user_pool, &ctx.accounts.farm_pool.key()) + receipt_count;
         require!(farm_pool.max_stake_count > staked_farm_count, StakingError::ExceedMaxCount);
         require!(farm_pool.is_stop == false, StakingError::PoolStopped);
 
-        let mint_metadata = &mut &ctx.accounts.mint_metadata;
-        let mut _collection : Pubkey = Pubkey::default();
-
-        msg!("Metadata Account: {:?}", ctx.accounts.mint_metadata.key());
-        let (metadata, _) = Pubkey::find_program_address(
-            &[
-                metaplex_token_metadata::state::PREFIX.as_bytes(),
-                metaplex_token_metadata::id().as_ref(),
-                ctx.accounts.nft_mint.key().as_ref(),
-            ],
-            &metaplex_token_metadata::id(),
-        );
-        require!(metadata == mint_metadata.key(), StakingError::InvaliedMetadata);
-
-        // verify metadata is legit
-        let nft_metadata = Metadata::from_account_info(mint_metadata)?;
-
-        if let Some(creators) = nft_metadata.data.creators {
-            _collection = creators[0].address;
-            
-            msg!("Collection= {:?}", _collection);
-        } else {
-            return Err(error!(StakingError::MetadataCreatorParseError));
-        };
-
-        let remaining_accs = &mut ctx.remaining_accounts.iter();
-        let wl_mint_proof = next_account_info(remaining_accs)?;
-        let wl_collection_proof = next_account_info(remaining_accs)?;
-
-        let (wl_mint_address, _bump) = Pubkey::find_program_address(
-            &[
-                ctx.accounts.nft_mint.key().as_ref(),
-                ctx.accounts.farm_pool.key().as_ref(),
-            ],
-            &staking::ID,
-        );
-        let (wl_collection_address, _bump) = Pubkey::find_program_address(
-            &[
-                _collection.key().as_ref(),
-                ctx.accounts.farm_pool.key().as_ref(),
-            ],
-            &staking::ID,
-        );
-        
-        require!(wl_mint_address.key() == wl_mint_proof.key(), 
-            StakingError::InvalidWhitelistAddress);
-        require!(wl_collection_address.key() == wl_collection_proof.key(), 
-            StakingError::InvalidWhitelistAddress);
-
-        require!(!wl_mint_proof.to_account_info().data_is_empty()
-                     || !wl_collection_proof.to_account_info().data_is_empty(),  
-        StakingError::InvalidWhitelistAddress);
-
-
+        let multiplier_bps = verify_whitelist(
+            &ctx.accounts.nft_mint,
+            &ctx.accounts.mint_metadata,
+            &ctx.accounts.farm_pool.key(),
+            &farm_pool.merkle_root,
+            merkle_proof.as_deref(),
+            ctx.remaining_accounts,
+        )?;
+        let rarity_weight_bps = verify_rarity(ctx.accounts.nft_mint.key, &farm_pool.rarity_root, rarity.as_ref())?;
 
         msg!("Stake Mint: {:?}", ctx.accounts.nft_mint.key());
         let timestamp = Clock::get()?.unix_timestamp;
-        user_pool.add_nft(ctx.accounts.nft_mint.key(), farm_pool.farm_number, timestamp);
+        user_pool.add_nft(ctx.accounts.nft_mint.key(), ctx.accounts.farm_pool.key(), farm_pool.farm_number, multiplier_bps, rarity_weight_bps, timestamp)?;
+        grow_user_pool(user_pool, &ctx.accounts.owner, &ctx.accounts.system_program)?;
         msg!("Staked Time: {}", timestamp);
         ctx.accounts.global_authority.total_staked_count += 1;
         farm_pool.staked_count += 1;
@@ -221,7 +372,15 @@ pub mod staking {
             CpiContext::new(token_program.clone().to_account_info(), cpi_accounts),
             1
         )?;
-        
+
+        emit!(StakeEvent {
+            farm_number: farm_pool.farm_number,
+            mint: ctx.accounts.nft_mint.key(),
+            owner: ctx.accounts.owner.key(),
+            multiplier_bps,
+            rarity_weight_bps,
+            timestamp,
+        });
         Ok(())
     }
 
@@ -231,101 +390,509 @@ pub mod staking {
         ctx: Context<UnstakeNftFromPool>,
         global_bump: u8,
     ) -> Result<()> {
-        let mut user_pool = ctx.accounts.user_pool.load_mut()?;
+        require!(ctx.accounts.farm_pool.load()?.cooldown_duration == 0, StakingError::CooldownRequired);
+        unstake_nft(ctx, global_bump, false)
+    }
+
+    /// Returns an NFT staked with the first program version, whose user pools weren't PDAs.
+    /// Its farms can't be read anymore and it paid no reward on unstake, so only the NFT comes back.
+    /// The legacy pool's rent goes back to the owner with its last NFT.
+    pub fn unstake_legacy_nft(
+        ctx: Context<UnstakeLegacyNft>,
+        global_bump: u8,
+    ) -> Result<()> {
+        let legacy_user_pool = &ctx.accounts.legacy_user_pool;
+        let owner = &ctx.accounts.owner;
+        require!(legacy_user_pool.key() == LegacyUserPool::address(owner.key)?, StakingError::InvalidUserPool);
+        let mut user_pool = LegacyUserPool::load(legacy_user_pool)?;
+        require!(user_pool.owner == owner.key(), StakingError::InvalidUserPool);
+        let staked_data = user_pool.remove_nft(ctx.accounts.nft_mint.key())?;
+        msg!("Staked Mint: {:?} Farm: {}", staked_data.mint, staked_data.farm_number);
+
+        let global_authority = &mut ctx.accounts.global_authority;
+        global_authority.total_staked_count -= 1;
+        let seeds = &[GLOBAL_AUTHORITY_SEED.as_bytes(), &[global_bump]];
+        let signer = &[&seeds[..]];
+        withdraw_nft(
+            &ctx.accounts.dest_nft_token_account,
+            &ctx.accounts.user_nft_token_account,
+            owner,
+            &global_authority.to_account_info(),
+            &ctx.accounts.token_program,
+            signer,
+        )?;
+
+        if user_pool.staking.is_empty() {
+            let starting_lamports: u64 = owner.lamports();
+            **owner.lamports.borrow_mut() = starting_lamports + legacy_user_pool.lamports();
+            **legacy_user_pool.lamports.borrow_mut() = 0;
+        } else {
+            user_pool.store(legacy_user_pool)?;
+        }
+
+        emit!(UnstakeLegacyNftEvent {
+            farm_number: staked_data.farm_number,
+            mint: staked_data.mint,
+            owner: owner.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Stops the reward accrual of a staked NFT and starts the farm's cooldown
+    #[access_control(user(&ctx.accounts.user_pool, &ctx.accounts.owner))]
+    pub fn begin_unstake(
+        ctx: Context<BeginUnstake>,
+    ) -> Result<()> {
+        let user_pool = &mut ctx.accounts.user_pool;
+        msg!("Staked Mint: {:?}", ctx.accounts.nft_mint.key());
+        let farm_pool = ctx.accounts.farm_pool.load()?;
+
+        let timestamp = Clock::get()?.unix_timestamp;
+        user_pool.request_unstake(ctx.accounts.nft_mint.key(), ctx.accounts.farm_pool.key(), &farm_pool, timestamp)?;
+        let cooldown_end_time = timestamp.saturating_add(farm_pool.cooldown_duration);
+        msg!("Unstake Requested Time: {} Cooldown End Time: {}", timestamp, cooldown_end_time);
+
+        emit!(BeginUnstakeEvent {
+            farm_number: farm_pool.farm_number,
+            mint: ctx.accounts.nft_mint.key(),
+            owner: ctx.accounts.owner.key(),
+            cooldown_end_time,
+            timestamp,
+        });
+        Ok(())
+    }
+
+    /// Returns an NFT whose unstake was requested once the farm's cooldown has passed
+    #[access_control(user(&ctx.accounts.user_pool, &ctx.accounts.owner))]
+    pub fn finalize_unstake(
+        ctx: Context<UnstakeNftFromPool>,
+        global_bump: u8,
+    ) -> Result<()> {
+        unstake_nft(ctx, global_bump, true)
+    }
+
+    #[access_control(user(&ctx.accounts.user_pool, &ctx.accounts.owner))]
+    pub fn claim_reward(
+        ctx: Context<ClaimReward>,
+        global_bump: u8
+    ) -> Result<()> {
+        let farm_pool = ctx.accounts.farm_pool.load_mut()?;
+        require!(farm_pool.is_stop == false, StakingError::PoolStopped);
+        require!(farm_pool.reward_mint == ctx.accounts.reward_mint.key(), StakingError::InvalidRewardMint);
+        
+        let user_pool = &mut ctx.accounts.user_pool;
         msg!("Staked Mint: {:?}", ctx.accounts.nft_mint.key());
-        let mut farm_pool = ctx.accounts.farm_pool.load_mut()?;
+
         let global_authority = &mut ctx.accounts.global_authority;
 
         let timestamp = Clock::get()?.unix_timestamp;
-        let reward: u64 = user_pool.remove_nft(ctx.accounts.nft_mint.key(), farm_pool.tier_duration, farm_pool.tier_rate, timestamp)?;
+        let reward: u64 = user_pool.claim_reward(ctx.accounts.nft_mint.key(), ctx.accounts.farm_pool.key(), &farm_pool, timestamp)?;
         msg!("Reward: {:?} Unstaked Time: {}", reward, timestamp);
-        global_authority.total_staked_count -= 1;
 
-        let token_account_info = &mut &ctx.accounts.user_nft_token_account;
-        let dest_token_account_info = &mut &ctx.accounts.dest_nft_token_account;
         let token_program = &mut &ctx.accounts.token_program;
         let seeds = &[GLOBAL_AUTHORITY_SEED.as_bytes(), &[global_bump]];
         let signer = &[&seeds[..]];
 
+        pay_reward(
+            &farm_pool,
+            &ctx.accounts.reward_vault,
+            &ctx.accounts.reward_mint,
+            &ctx.accounts.user_reward_account.to_account_info(),
+            &global_authority.to_account_info(),
+            &token_program.to_account_info(),
+            signer,
+            reward,
+        )?;
+
+        emit!(ClaimEvent {
+            farm_number: farm_pool.farm_number,
+            mint: ctx.accounts.nft_mint.key(),
+            owner: ctx.accounts.owner.key(),
+            reward,
+            timestamp,
+        });
+        Ok(())
+    }
+
+    /// Claims the reward of every staked NFT in one mint.
+    /// Each farm of the staked NFTs is passed as a `[farm_pool, reward_vault]` pair of remaining accounts,
+    /// `farm_number` limits the claim to the NFTs staked in that farm.
+    /// All claimed farms have to pay the same reward mint.
+    /// Farms paying from a vault are transferred separately, the rest is minted at once.
+    #[access_control(user(&ctx.accounts.user_pool, &ctx.accounts.owner))]
+    pub fn claim_all<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimAll<'info>>,
+        global_bump: u8,
+        farm_number: Option<u64>,
+    ) -> Result<()> {
+        let farm_chunks = ctx.remaining_accounts.chunks_exact(2);
+        require!(farm_chunks.remainder().is_empty(), StakingError::InvalidInput);
+        let user_pool = &mut ctx.accounts.user_pool;
+        let global_authority = &mut ctx.accounts.global_authority;
+        let token_program = &mut &ctx.accounts.token_program;
+        let seeds = &[GLOBAL_AUTHORITY_SEED.as_bytes(), &[global_bump]];
+        let signer = &[&seeds[..]];
+
+        let timestamp = Clock::get()?.unix_timestamp;
+        let mut total_reward: u64 = 0;
+        let mut minted_reward: u64 = 0;
+        let mut claimed_farms: Vec<Pubkey> = vec![];
+        for farm_accounts in farm_chunks {
+            let farm_loader = AccountLoader::<FarmData>::try_from(&farm_accounts[0])?;
+            let farm_pool = farm_loader.load()?;
+            let farm_address = Pubkey::create_program_address(
+                &[FARM_SEED.as_ref(), farm_pool.farm_number.to_le_bytes().as_ref(), &[farm_pool.bump]],
+                &crate::ID,
+            ).map_err(|_| error!(StakingError::InvalidFarmPool))?;
+            require!(farm_address == farm_accounts[0].key(), StakingError::InvalidFarmPool);
+            if farm_number.is_some() && farm_number != Some(farm_pool.farm_number) {
+                continue;
+            }
+            claimed_farms.push(farm_accounts[0].key());
+            if farm_pool.is_stop {
+                msg!("Farm {} is stopped", farm_pool.farm_number);
+                continue;
+            }
+            require!(farm_pool.reward_mint == ctx.accounts.reward_mint.key(), StakingError::InvalidRewardMint);
+
+            let reward: u64 = user_pool.claim_farm_reward(farm_accounts[0].key(), &farm_pool, timestamp)?;
+            msg!("Farm: {} Reward: {:?}", farm_pool.farm_number, reward);
+            total_reward = total_reward.checked_add(reward).ok_or(StakingError::RewardOverflow)?;
+            if farm_pool.reward_vault == Pubkey::default() {
+                minted_reward += reward;
+            } else if reward > 0 {
+                pay_reward(
+                    &farm_pool,
+                    &farm_accounts[1],
+                    &ctx.accounts.reward_mint,
+                    &ctx.accounts.user_reward_account.to_account_info(),
+                    &global_authority.to_account_info(),
+                    &token_program.to_account_info(),
+                    signer,
+                    reward,
+                )?;
+            }
+        }
+
+        // Every staked NFT in the claimed scope has to be covered by a farm account
+        for staked_data in user_pool.staking.iter() {
+            if farm_number.is_some() && farm_number != Some(staked_data.farm_number) {
+                continue;
+            }
+            require!(claimed_farms.contains(&staked_data.farm), StakingError::MissingFarmAccount);
+        }
+        msg!("Total Reward: {:?} Claimed Time: {}", total_reward, timestamp);
+
+        if minted_reward > 0 {
+            let cpi_accounts = MintTo {
+                mint: ctx.accounts.reward_mint.to_account_info().clone(),
+                to: ctx.accounts.user_reward_account.to_account_info().clone(),
+                authority: global_authority.to_account_info().clone(),
+            };
+            token::mint_to(
+                CpiContext::new_with_signer(token_program.clone().to_account_info(), cpi_accounts, signer),
+                minted_reward
+            )?;
+        }
+
+        emit!(ClaimAllEvent {
+            farm_number,
+            owner: ctx.accounts.owner.key(),
+            reward: total_reward,
+            timestamp,
+        });
+        Ok(())
+    }
+
+    /// Stakes an NFT into its own receipt account instead of the user pool.
+    /// Receipts and the owner's user pool entries together count against the farm's `max_stake_count`.
+    /// The cap is kept by the owner's receipt counter of the farm, which every receipt stake writes,
+    /// so one owner's receipt stakes in one farm still run one at a time. Other owners and farms don't wait.
+    pub fn stake_nft_to_receipt(
+        ctx: Context<StakeNftToReceipt>,
+        _global_bump: u8,
+        merkle_proof: Option<Vec<[u8; 32]>>,
+        rarity: Option<RarityProof>,
+    ) -> Result<()> {
+        let mut farm_pool = ctx.accounts.farm_pool.load_mut()?;
+        let pool_count = load_initialized::<UserPool>(&ctx.accounts.user_pool)?
+            .map_or(0, |user_pool| farm_stake_count(&user_pool, &ctx.accounts.farm_pool.key()));
+        let receipt_counter = &mut ctx.accounts.receipt_counter;
+        require!(farm_pool.max_stake_count > pool_count + receipt_counter.count, StakingError::ExceedMaxCount);
+        require!(!farm_pool.is_stop, StakingError::PoolStopped);
+
+        let multiplier_bps = verify_whitelist(
+            &ctx.accounts.nft_mint,
+            &ctx.accounts.mint_metadata,
+            &ctx.accounts.farm_pool.key(),
+            &farm_pool.merkle_root,
+            merkle_proof.as_deref(),
+            ctx.remaining_accounts,
+        )?;
+        let rarity_weight_bps = verify_rarity(ctx.accounts.nft_mint.key, &farm_pool.rarity_root, rarity.as_ref())?;
+
+        msg!("Stake Mint: {:?}", ctx.accounts.nft_mint.key());
+        let timestamp = Clock::get()?.unix_timestamp;
+        let stake_receipt = &mut ctx.accounts.stake_receipt;
+        stake_receipt.owner = ctx.accounts.owner.key();
+        stake_receipt.bump = *ctx.bumps.get("stake_receipt").unwrap();
+        stake_receipt.stake = StakedData {
+            mint: ctx.accounts.nft_mint.key(),
+            farm: ctx.accounts.farm_pool.key(),
+            farm_number: farm_pool.farm_number,
+            staked_time: timestamp,
+            claimed_time: timestamp,
+            unstake_requested_time: 0,
+            multiplier_bps,
+            rarity_weight_bps,
+        };
+        msg!("Staked Time: {}", timestamp);
+        receipt_counter.bump = *ctx.bumps.get("receipt_counter").unwrap();
+        receipt_counter.count += 1;
+        ctx.accounts.global_authority.total_staked_count += 1;
+        farm_pool.staked_count += 1;
+
         let cpi_accounts = Transfer {
-            from: dest_token_account_info.to_account_info().clone(),
-            to: token_account_info.to_account_info().clone(),
-            authority: global_authority.to_account_info()
+            from: ctx.accounts.user_nft_token_account.to_account_info(),
+            to: ctx.accounts.dest_nft_token_account.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
         };
         token::transfer(
-            CpiContext::new_with_signer(token_program.clone().to_account_info(), cpi_accounts, signer),
+            CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
             1
         )?;
 
+        emit!(StakeEvent {
+            farm_number: farm_pool.farm_number,
+            mint: ctx.accounts.nft_mint.key(),
+            owner: ctx.accounts.owner.key(),
+            multiplier_bps,
+            rarity_weight_bps,
+            timestamp,
+        });
+        Ok(())
+    }
+
+    /// Stops the reward accrual of a receipt stake and starts the farm's cooldown
+    pub fn begin_receipt_unstake(
+        ctx: Context<BeginReceiptUnstake>,
+    ) -> Result<()> {
+        let farm_pool = ctx.accounts.farm_pool.load()?;
+        let stake_receipt = &mut ctx.accounts.stake_receipt;
+        msg!("Staked Mint: {:?}", stake_receipt.stake.mint);
+
+        let timestamp = Clock::get()?.unix_timestamp;
+        stake_receipt.stake.request_unstake(&farm_pool, timestamp)?;
+        let cooldown_end_time = timestamp.saturating_add(farm_pool.cooldown_duration);
+        msg!("Unstake Requested Time: {} Cooldown End Time: {}", timestamp, cooldown_end_time);
+
+        emit!(BeginUnstakeEvent {
+            farm_number: farm_pool.farm_number,
+            mint: stake_receipt.stake.mint,
+            owner: ctx.accounts.owner.key(),
+            cooldown_end_time,
+            timestamp,
+        });
+        Ok(())
+    }
+
+    /// Returns the NFT of a receipt stake, pays its reward and closes the receipt,
+    /// and the owner's receipt counter of the farm with its last receipt.
+    /// On farms with a cooldown the unstake has to be requested first.
+    pub fn unstake_nft_from_receipt(
+        ctx: Context<UnstakeNftFromReceipt>,
+        global_bump: u8,
+    ) -> Result<()> {
+        let mut farm_pool = ctx.accounts.farm_pool.load_mut()?;
+        require!(farm_pool.reward_mint == ctx.accounts.reward_mint.key(), StakingError::InvalidRewardMint);
+        let staked_data = ctx.accounts.stake_receipt.stake;
+        msg!("Staked Mint: {:?}", staked_data.mint);
+
+        let timestamp = Clock::get()?.unix_timestamp;
+        farm_pool.check_unlocked(staked_data.staked_time, timestamp)?;
+        if farm_pool.cooldown_duration != 0 {
+            farm_pool.check_cooldown(&staked_data, timestamp)?;
+        }
+        let reward = staked_data.unstake_reward(&farm_pool, timestamp);
+        msg!("Reward: {:?} Unstaked Time: {}", reward, timestamp);
+        let global_authority = &mut ctx.accounts.global_authority;
+        global_authority.total_staked_count -= 1;
         farm_pool.staked_count -= 1;
+        ctx.accounts.receipt_counter.count -= 1;
+        if ctx.accounts.receipt_counter.count == 0 {
+            ctx.accounts.receipt_counter.close(ctx.accounts.owner.to_account_info())?;
+        }
+
+        let seeds = &[GLOBAL_AUTHORITY_SEED.as_bytes(), &[global_bump]];
+        let signer = &[&seeds[..]];
 
-        // TODO
-        // let token_program = &mut &ctx.accounts.token_program;
-        // let seeds = &[GLOBAL_AUTHORITY_SEED.as_bytes(), &[global_bump]];
-        // let signer = &[&seeds[..]];
-
-        // let cpi_accounts = MintTo {
-        //     mint: ctx.accounts.reward_mint.to_account_info().clone(),
-        //     to: ctx.accounts.user_reward_account.to_account_info().clone(),
-        //     authority: global_authority.to_account_info().clone(),
-        // };
-        // token::mint_to(
-        //     CpiContext::new_with_signer(token_program.clone().to_account_info(), cpi_accounts, signer),
-        //     reward
-        // )?;
-
-        invoke_signed(
-            &spl_token::instruction::close_account(
-                token_program.key,
-                &dest_token_account_info.key(),
-                ctx.accounts.owner.key,
-                &ctx.accounts.global_authority.key(),
-                &[],
-            )?,
-            &[
-                token_program.clone().to_account_info(),
-                dest_token_account_info.to_account_info().clone(),
-                ctx.accounts.owner.to_account_info().clone(),
-                ctx.accounts.global_authority.to_account_info().clone(),
-            ],
+        withdraw_nft(
+            &ctx.accounts.dest_nft_token_account,
+            &ctx.accounts.user_nft_token_account,
+            &ctx.accounts.owner,
+            &global_authority.to_account_info(),
+            &ctx.accounts.token_program,
             signer,
         )?;
-        
+        let (penalty, unpaid_reward) = pay_unstake_reward(
+            &farm_pool,
+            &staked_data,
+            reward,
+            timestamp,
+            &ctx.accounts.reward_vault,
+            &ctx.accounts.reward_mint,
+            &ctx.accounts.user_reward_account.to_account_info(),
+            &ctx.accounts.penalty_treasury,
+            &global_authority.to_account_info(),
+            &ctx.accounts.token_program,
+            signer,
+        )?;
+
+        emit!(UnstakeEvent {
+            farm_number: farm_pool.farm_number,
+            mint: staked_data.mint,
+            owner: ctx.accounts.owner.key(),
+            reward,
+            penalty,
+            unpaid_reward,
+            timestamp,
+        });
         Ok(())
     }
 
-    #[access_control(user(&ctx.accounts.user_pool, &ctx.accounts.owner))]
-    pub fn claim_reward(
-        ctx: Context<ClaimReward>,
-        global_bump: u8
+    pub fn claim_receipt_reward(
+        ctx: Context<ClaimReceiptReward>,
+        global_bump: u8,
     ) -> Result<()> {
-        let farm_pool = ctx.accounts.farm_pool.load_mut()?;
-        require!(farm_pool.is_stop == false, StakingError::PoolStopped);
-        
-        let mut user_pool = ctx.accounts.user_pool.load_mut()?;
-        msg!("Staked Mint: {:?}", ctx.accounts.nft_mint.key());
+        let farm_pool = ctx.accounts.farm_pool.load()?;
+        require!(!farm_pool.is_stop, StakingError::PoolStopped);
+        require!(farm_pool.reward_mint == ctx.accounts.reward_mint.key(), StakingError::InvalidRewardMint);
 
-        let global_authority = &mut ctx.accounts.global_authority;
+        let stake_receipt = &mut ctx.accounts.stake_receipt;
+        msg!("Staked Mint: {:?}", stake_receipt.stake.mint);
 
         let timestamp = Clock::get()?.unix_timestamp;
-        let reward: u64 = user_pool.claim_reward(ctx.accounts.nft_mint.key(), farm_pool.tier_duration, farm_pool.tier_rate, timestamp)?;
-        msg!("Reward: {:?} Unstaked Time: {}", reward, timestamp);
+        let reward = stake_receipt.stake.claim(&farm_pool, timestamp)?;
+        msg!("Reward: {:?} Claimed Time: {}", reward, timestamp);
 
-        // TODO
-        let token_program = &mut &ctx.accounts.token_program;
         let seeds = &[GLOBAL_AUTHORITY_SEED.as_bytes(), &[global_bump]];
         let signer = &[&seeds[..]];
 
-        let cpi_accounts = MintTo {
-            mint: ctx.accounts.reward_mint.to_account_info().clone(),
-            to: ctx.accounts.user_reward_account.to_account_info().clone(),
-            authority: global_authority.to_account_info().clone(),
+        pay_reward(
+            &farm_pool,
+            &ctx.accounts.reward_vault,
+            &ctx.accounts.reward_mint,
+            &ctx.accounts.user_reward_account.to_account_info(),
+            &ctx.accounts.global_authority.to_account_info(),
+            &ctx.accounts.token_program.to_account_info(),
+            signer,
+            reward,
+        )?;
+
+        emit!(ClaimEvent {
+            farm_number: farm_pool.farm_number,
+            mint: stake_receipt.stake.mint,
+            owner: ctx.accounts.owner.key(),
+            reward,
+            timestamp,
+        });
+        Ok(())
+    }
+
+    /// Moves an NFT staked in the user pool to its own receipt.
+    /// The stake keeps its times, so neither the reward nor the lock restart.
+    #[access_control(user(&ctx.accounts.user_pool, &ctx.accounts.owner))]
+    pub fn migrate_to_receipt(
+        ctx: Context<MigrateToReceipt>,
+    ) -> Result<()> {
+        let farm_pool = ctx.accounts.farm_pool.load()?;
+        let staked_data = ctx.accounts.user_pool.take_nft(ctx.accounts.nft_mint.key(), ctx.accounts.farm_pool.key())?;
+        msg!("Migrated Mint: {:?}", staked_data.mint);
+
+        let stake_receipt = &mut ctx.accounts.stake_receipt;
+        stake_receipt.owner = ctx.accounts.owner.key();
+        stake_receipt.bump = *ctx.bumps.get("stake_receipt").unwrap();
+        stake_receipt.stake = staked_data;
+        let receipt_counter = &mut ctx.accounts.receipt_counter;
+        receipt_counter.bump = *ctx.bumps.get("receipt_counter").unwrap();
+        receipt_counter.count += 1;
+
+        emit!(MigrateToReceiptEvent {
+            farm_number: farm_pool.farm_number,
+            mint: staked_data.mint,
+            owner: ctx.accounts.owner.key(),
+            stake_receipt: stake_receipt.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn set_unstake_penalty(
+        ctx: Context<SetUnstakePenalty>,
+        penalty_duration: i64,
+        penalty_bps: u64,
+        penalty_treasury: Option<Pubkey>,
+    ) -> Result<()> {
+        require!(penalty_duration >= 0, StakingError::InvalidInput);
+        require!(penalty_bps <= BPS_DENOMINATOR, StakingError::InvalidInput);
+
+        let mut farm_pool = ctx.accounts.farm_pool.load_mut()?;
+        farm_pool.penalty_duration = penalty_duration;
+        farm_pool.penalty_bps = penalty_bps;
+        farm_pool.penalty_treasury = penalty_treasury.unwrap_or_default();
+
+        emit!(SetUnstakePenaltyEvent {
+            farm_number: farm_pool.farm_number,
+            penalty_duration,
+            penalty_bps,
+            penalty_treasury,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn init_reward_vault(
+        ctx: Context<InitRewardVault>,
+    ) -> Result<()> {
+        let mut farm_pool = ctx.accounts.farm_pool.load_mut()?;
+        require!(farm_pool.reward_mint == ctx.accounts.reward_mint.key(), StakingError::InvalidRewardMint);
+        farm_pool.reward_vault = ctx.accounts.reward_vault.key();
+        msg!("Farm {} pays rewards from vault {:?}", farm_pool.farm_number, farm_pool.reward_vault);
+
+        emit!(InitRewardVaultEvent {
+            farm_number: farm_pool.farm_number,
+            reward_vault: farm_pool.reward_vault,
+            reward_mint: ctx.accounts.reward_mint.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn fund_reward_vault(
+        ctx: Context<FundRewardVault>,
+        amount: u64,
+    ) -> Result<()> {
+        let farm_pool = ctx.accounts.farm_pool.load()?;
+        require!(farm_pool.reward_vault == ctx.accounts.reward_vault.key(), StakingError::InvalidRewardVault);
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.admin_reward_account.to_account_info().clone(),
+            to: ctx.accounts.reward_vault.to_account_info().clone(),
+            authority: ctx.accounts.admin.to_account_info().clone(),
         };
-        token::mint_to(
-            CpiContext::new_with_signer(token_program.clone().to_account_info(), cpi_accounts, signer),
-            reward
+        token::transfer(
+            CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+            amount
         )?;
 
+        emit!(FundRewardVaultEvent {
+            farm_number: farm_pool.farm_number,
+            reward_vault: farm_pool.reward_vault,
+            admin: ctx.accounts.admin.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         Ok(())
     }
 
@@ -335,8 +902,6 @@ pub mod staking {
     ) -> Result<()> {
         let global_authority = &mut ctx.accounts.global_authority;
         let new_authority = &mut ctx.accounts.new_authority;
-        require!(ctx.accounts.admin.key() == global_authority.super_admin, StakingError::InvalidSuperOwner);
-        
         msg!("Transfer mintAuthority: {:?}", new_authority);
         
         let reward_mint = &mut ctx.accounts.reward_mint;
@@ -355,6 +920,11 @@ pub mod staking {
             Some(new_authority.key()),
         )?;
 
+        emit!(TransferMintAuthorityEvent {
+            reward_mint: reward_mint.key(),
+            new_authority: new_authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         Ok(())
     }
 
@@ -365,8 +935,6 @@ pub mod staking {
     ) -> Result<()> {
         let global_authority = &mut ctx.accounts.global_authority;
         let user_reward_account = &mut ctx.accounts.user_reward_account;
-        // require!(ctx.accounts.admin.key() == global_authority.super_admin, StakingError::InvalidSuperOwner);
-        
         msg!("Transfer mintAuthority: {:?}", user_reward_account.key());
         
         let token_program = &mut ctx.accounts.token_program;
@@ -383,6 +951,13 @@ pub mod staking {
             amount
         )?;
 
+        emit!(MintToAccountEvent {
+            reward_mint: ctx.accounts.reward_mint.key(),
+            user_reward_account: ctx.accounts.user_reward_account.key(),
+            admin: ctx.accounts.admin.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         Ok(())
     }
 }
@@ -417,49 +992,223 @@ pub struct Initialize<'info> {
 }
 
 #[derive(Accounts)]
-pub struct InitializeUserPool<'info> {
-    #[account(zero)]
-    pub user_pool: AccountLoader<'info, UserPool>,
-
-    #[account(mut)]
-    pub owner: Signer<'info>,
-}
-
-
-#[derive(Accounts)]
-pub struct CreateFarm<'info> {
+pub struct ProposeAdmin<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
-    
     #[account(
-        mut,
         seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
         bump,
+        constraint = global_authority.super_admin == admin.key() @ StakingError::InvalidSuperOwner,
     )]
     pub global_authority: Account<'info, GlobalPool>,
-
-    #[account(zero)]
-    pub farm_pool: AccountLoader<'info, FarmData>,
-}
-
-#[derive(Accounts)]
-pub struct UpdateFarm<'info> {
+    #[account(
+        init,
+        seeds = [ADMIN_PROPOSAL_SEED.as_ref()],
+        bump,
+        space = 8 + 40,
+        payer = admin
+    )]
+    pub admin_proposal: Account<'info, AdminProposal>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAdminProposal<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+        constraint = global_authority.super_admin == admin.key() @ StakingError::InvalidSuperOwner,
+    )]
+    pub global_authority: Account<'info, GlobalPool>,
+    #[account(
+        mut,
+        close = admin,
+        seeds = [ADMIN_PROPOSAL_SEED.as_ref()],
+        bump,
+    )]
+    pub admin_proposal: Account<'info, AdminProposal>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(mut)]
+    pub new_admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Account<'info, GlobalPool>,
+    #[account(
+        mut,
+        close = new_admin,
+        seeds = [ADMIN_PROPOSAL_SEED.as_ref()],
+        bump,
+        constraint = admin_proposal.proposed_admin == new_admin.key() @ StakingError::InvalidProposedAdmin,
+    )]
+    pub admin_proposal: Account<'info, AdminProposal>,
+}
+
+#[derive(Accounts)]
+pub struct InitAdminRoles<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
+    #[account(
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+        constraint = global_authority.super_admin == admin.key() @ StakingError::InvalidSuperOwner,
+    )]
+    pub global_authority: Account<'info, GlobalPool>,
+    #[account(
+        init,
+        seeds = [ADMIN_ROLES_SEED.as_ref()],
+        bump,
+        space = AdminRoles::LEN,
+        payer = admin
+    )]
+    pub admin_roles: Box<Account<'info, AdminRoles>>,
+    pub system_program: Program<'info, System>,
+}
 
+#[derive(Accounts)]
+pub struct GrantRole<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+        constraint = global_authority.super_admin == admin.key() @ StakingError::InvalidSuperOwner,
+    )]
+    pub global_authority: Account<'info, GlobalPool>,
     #[account(
         mut,
+        seeds = [ADMIN_ROLES_SEED.as_ref()],
+        bump = admin_roles.bump,
+    )]
+    pub admin_roles: Box<Account<'info, AdminRoles>>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    pub admin: Signer<'info>,
+    #[account(
         seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
         bump,
+        constraint = global_authority.super_admin == admin.key() @ StakingError::InvalidSuperOwner,
     )]
     pub global_authority: Account<'info, GlobalPool>,
+    #[account(
+        mut,
+        seeds = [ADMIN_ROLES_SEED.as_ref()],
+        bump = admin_roles.bump,
+    )]
+    pub admin_roles: Box<Account<'info, AdminRoles>>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeUserPool<'info> {
+    #[account(
+        init,
+        seeds = [USER_POOL_SEED.as_ref(), owner.key().as_ref()],
+        bump,
+        space = UserPool::space(0),
+        payer = owner
+    )]
+    pub user_pool: Box<Account<'info, UserPool>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+
+#[derive(Accounts)]
+pub struct CloseUserPool<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [USER_POOL_SEED.as_ref(), owner.key().as_ref()],
+        bump = user_pool.bump,
+    )]
+    pub user_pool: Box<Account<'info, UserPool>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+
+#[derive(Accounts)]
+#[instruction(
+    tier_duration: Vec<i64>,
+    tier_rate: Vec<u64>,
+    max_stake_count: u64,
+    min_lock_duration: i64,
+    cooldown_duration: i64,
+    count: u64,
+)]
+pub struct CreateFarm<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
     
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Account<'info, GlobalPool>,
+
+    #[account(
+        seeds = [ADMIN_ROLES_SEED.as_ref()],
+        bump = admin_roles.bump,
+        constraint = admin_roles.allows(&global_authority.super_admin, &admin.key(), Role::FarmManager) @ StakingError::MissingRole,
+    )]
+    pub admin_roles: Box<Account<'info, AdminRoles>>,
+
+    #[account(
+        init,
+        seeds = [FARM_SEED.as_ref(), count.to_le_bytes().as_ref()],
+        bump,
+        space = 8 + std::mem::size_of::<FarmData>(),
+        payer = admin
+    )]
+    pub farm_pool: AccountLoader<'info, FarmData>,
+
+    pub reward_mint: Box<Account<'info, Mint>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateFarm<'info> {
     #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Account<'info, GlobalPool>,
+
+    #[account(
+        seeds = [ADMIN_ROLES_SEED.as_ref()],
+        bump = admin_roles.bump,
+        constraint = admin_roles.allows(&global_authority.super_admin, &admin.key(), Role::FarmManager) @ StakingError::MissingRole,
+    )]
+    pub admin_roles: Box<Account<'info, AdminRoles>>,
+    
+    #[account(
+        mut,
+        seeds = [FARM_SEED.as_ref(), farm_pool.load()?.farm_number.to_le_bytes().as_ref()],
+        bump = farm_pool.load()?.bump,
+    )]
     pub farm_pool: AccountLoader<'info, FarmData>,
 }
 
 
 #[derive(Accounts)]
+#[instruction(kind: WhitelistKind)]
 pub struct AddWhitelist<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
@@ -469,7 +1218,17 @@ pub struct AddWhitelist<'info> {
         bump,
     )]
     pub global_authority: Account<'info, GlobalPool>,
-    #[account(mut)]
+    #[account(
+        seeds = [ADMIN_ROLES_SEED.as_ref()],
+        bump = admin_roles.bump,
+        constraint = admin_roles.allows(&global_authority.super_admin, &admin.key(), Role::WhitelistManager) @ StakingError::MissingRole,
+    )]
+    pub admin_roles: Box<Account<'info, AdminRoles>>,
+    #[account(
+        mut,
+        seeds = [FARM_SEED.as_ref(), farm_pool.load()?.farm_number.to_le_bytes().as_ref()],
+        bump = farm_pool.load()?.bump,
+    )]
     pub farm_pool: AccountLoader<'info, FarmData>,
     /// CHECK: This is not dangerous because we don't read or write from this account
     #[account(mut)]
@@ -477,9 +1236,9 @@ pub struct AddWhitelist<'info> {
 
     #[account(
         init,
-        seeds = [whitelist_address.key().as_ref(), farm_pool.key().as_ref()],
+        seeds = [whitelist_address.key().as_ref(), farm_pool.key().as_ref(), kind.seed()],
         bump,
-        space = 8 + 33,
+        space = WhitelistProof::LEN,
         payer = admin
     )]
     pub whitelist_proof: Account<'info, WhitelistProof>,
@@ -498,18 +1257,25 @@ pub struct RemoveWhitelist<'info> {
         bump,
     )]
     pub global_authority: Account<'info, GlobalPool>,
-    #[account(mut)]
+    #[account(
+        seeds = [ADMIN_ROLES_SEED.as_ref()],
+        bump = admin_roles.bump,
+        constraint = admin_roles.allows(&global_authority.super_admin, &admin.key(), Role::WhitelistManager) @ StakingError::MissingRole,
+    )]
+    pub admin_roles: Box<Account<'info, AdminRoles>>,
+    #[account(
+        mut,
+        seeds = [FARM_SEED.as_ref(), farm_pool.load()?.farm_number.to_le_bytes().as_ref()],
+        bump = farm_pool.load()?.bump,
+    )]
     pub farm_pool: AccountLoader<'info, FarmData>,
     /// CHECK: This is not dangerous because we don't read or write from this account
     #[account(mut)]
     pub whitelist_address: AccountInfo<'info>,
 
-    #[account(
-        mut,
-        seeds = [whitelist_address.key().as_ref(), farm_pool.key().as_ref()],
-        bump,
-    )]
-    pub whitelist_proof: Account<'info, WhitelistProof>,
+    /// CHECK: Checked against the addresses of the entry's kind in the instruction
+    #[account(mut)]
+    pub whitelist_proof: AccountInfo<'info>,
 
 }
 
@@ -524,10 +1290,66 @@ pub struct SetStop<'info> {
         bump,
     )]
     pub global_authority: Account<'info, GlobalPool>,
+    #[account(
+        seeds = [ADMIN_ROLES_SEED.as_ref()],
+        bump = admin_roles.bump,
+        constraint = admin_roles.allows(&global_authority.super_admin, &admin.key(), Role::FarmManager) @ StakingError::MissingRole,
+    )]
+    pub admin_roles: Box<Account<'info, AdminRoles>>,
+    #[account(
+        mut,
+        seeds = [FARM_SEED.as_ref(), farm_pool.load()?.farm_number.to_le_bytes().as_ref()],
+        bump = farm_pool.load()?.bump,
+    )]
+    pub farm_pool: AccountLoader<'info, FarmData>,
+}
+
+
+#[derive(Accounts)]
+pub struct SetMerkleRoot<'info> {
     #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Account<'info, GlobalPool>,
+    #[account(
+        seeds = [ADMIN_ROLES_SEED.as_ref()],
+        bump = admin_roles.bump,
+        constraint = admin_roles.allows(&global_authority.super_admin, &admin.key(), Role::WhitelistManager) @ StakingError::MissingRole,
+    )]
+    pub admin_roles: Box<Account<'info, AdminRoles>>,
+    #[account(
+        mut,
+        seeds = [FARM_SEED.as_ref(), farm_pool.load()?.farm_number.to_le_bytes().as_ref()],
+        bump = farm_pool.load()?.bump,
+    )]
     pub farm_pool: AccountLoader<'info, FarmData>,
 }
 
+#[derive(Accounts)]
+pub struct SetRarityRoot<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Account<'info, GlobalPool>,
+    #[account(
+        seeds = [ADMIN_ROLES_SEED.as_ref()],
+        bump = admin_roles.bump,
+        constraint = admin_roles.allows(&global_authority.super_admin, &admin.key(), Role::FarmManager) @ StakingError::MissingRole,
+    )]
+    pub admin_roles: Box<Account<'info, AdminRoles>>,
+    #[account(
+        mut,
+        seeds = [FARM_SEED.as_ref(), farm_pool.load()?.farm_number.to_le_bytes().as_ref()],
+        bump = farm_pool.load()?.bump,
+    )]
+    pub farm_pool: AccountLoader<'info, FarmData>,
+}
 
 #[derive(Accounts)]
 #[instruction(bump: u8)]
@@ -535,10 +1357,25 @@ pub struct StakeNftToPool<'info> {
     #[account(mut)]
     pub owner: Signer<'info>,
     
-    #[account(mut)]
-    pub user_pool: AccountLoader<'info, UserPool>,
+    #[account(
+        mut,
+        seeds = [USER_POOL_SEED.as_ref(), owner.key().as_ref()],
+        bump = user_pool.bump,
+    )]
+    pub user_pool: Box<Account<'info, UserPool>>,
 
-    #[account(mut)]
+    /// CHECK: Read when the owner has receipt stakes in the farm
+    #[account(
+        seeds = [RECEIPT_COUNTER_SEED.as_ref(), farm_pool.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub receipt_counter: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [FARM_SEED.as_ref(), farm_pool.load()?.farm_number.to_le_bytes().as_ref()],
+        bump = farm_pool.load()?.bump,
+    )]
     pub farm_pool: AccountLoader<'info, FarmData>,
 
     #[account(
@@ -568,14 +1405,15 @@ pub struct StakeNftToPool<'info> {
     /// the mint metadata
     #[account(
         mut,
-        constraint = mint_metadata.owner == &metaplex_token_metadata::ID
+        constraint = mint_metadata.owner == &mpl_token_metadata::ID
     )]
     /// CHECK: This is not dangerous because we don't read or write from this account
     pub mint_metadata: AccountInfo<'info>,
     pub token_program: Program<'info, Token>,
     /// CHECK: This is not dangerous because we don't read or write from this account
-    #[account(constraint = token_metadata_program.key == &metaplex_token_metadata::ID)]
+    #[account(constraint = token_metadata_program.key == &mpl_token_metadata::ID)]
     pub token_metadata_program: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
 }
 
 
@@ -585,10 +1423,18 @@ pub struct UnstakeNftFromPool<'info> {
     #[account(mut)]
     pub owner: Signer<'info>,
     
-    #[account(mut)]
-    pub user_pool: AccountLoader<'info, UserPool>,
+    #[account(
+        mut,
+        seeds = [USER_POOL_SEED.as_ref(), owner.key().as_ref()],
+        bump = user_pool.bump,
+    )]
+    pub user_pool: Box<Account<'info, UserPool>>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [FARM_SEED.as_ref(), farm_pool.load()?.farm_number.to_le_bytes().as_ref()],
+        bump = farm_pool.load()?.bump,
+    )]
     pub farm_pool: AccountLoader<'info, FarmData>,
 
     #[account(
@@ -624,6 +1470,14 @@ pub struct UnstakeNftFromPool<'info> {
     #[account(mut)]
     pub reward_mint: AccountInfo<'info>,
 
+    /// CHECK: Only used and checked against the farm when the farm pays from a vault
+    #[account(mut)]
+    pub reward_vault: AccountInfo<'info>,
+
+    /// CHECK: Only used and checked against the farm when a penalty goes to its treasury
+    #[account(mut)]
+    pub penalty_treasury: AccountInfo<'info>,
+
     /// CHECK: This is not dangerous because we don't read or write from this account
     pub nft_mint: AccountInfo<'info>,
     pub token_program: Program<'info, Token>,
@@ -632,15 +1486,13 @@ pub struct UnstakeNftFromPool<'info> {
 
 #[derive(Accounts)]
 #[instruction(bump: u8)]
-pub struct ClaimReward<'info> {
+pub struct UnstakeLegacyNft<'info> {
     #[account(mut)]
     pub owner: Signer<'info>,
- 
-    #[account(mut)]
-    pub user_pool: AccountLoader<'info, UserPool>,
 
+    /// CHECK: Checked against the owner's legacy address and read by `LegacyUserPool::load`
     #[account(mut)]
-    pub farm_pool: AccountLoader<'info, FarmData>,
+    pub legacy_user_pool: AccountInfo<'info>,
 
     #[account(
         mut,
@@ -651,14 +1503,18 @@ pub struct ClaimReward<'info> {
 
     #[account(
         mut,
-        constraint = user_reward_account.mint == *reward_mint.key,
-        constraint = user_reward_account.owner == *owner.key,
+        constraint = user_nft_token_account.mint == nft_mint.key(),
+        constraint = user_nft_token_account.owner == *owner.key,
     )]
-    pub user_reward_account: Box<Account<'info, TokenAccount>>,
+    pub user_nft_token_account: Account<'info, TokenAccount>,
 
-    /// CHECK: This is not dangerous because we don't read or write from this account
-    #[account(mut)]
-    pub reward_mint: AccountInfo<'info>,
+    #[account(
+        mut,
+        constraint = dest_nft_token_account.mint == nft_mint.key(),
+        constraint = dest_nft_token_account.owner == global_authority.key(),
+        constraint = dest_nft_token_account.amount == 1,
+    )]
+    pub dest_nft_token_account: Account<'info, TokenAccount>,
 
     /// CHECK: This is not dangerous because we don't read or write from this account
     pub nft_mint: AccountInfo<'info>,
@@ -667,36 +1523,521 @@ pub struct ClaimReward<'info> {
 
 
 #[derive(Accounts)]
-pub struct TransferAuthority<'info> {
+pub struct BeginUnstake<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
     #[account(
         mut,
-        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
-        bump,
+        seeds = [USER_POOL_SEED.as_ref(), owner.key().as_ref()],
+        bump = user_pool.bump,
     )]
-    pub global_authority: Box<Account<'info, GlobalPool>>,
-
-    #[account(mut)]
-    pub admin: Signer<'info>,
+    pub user_pool: Box<Account<'info, UserPool>>,
 
-    #[account(mut)]
-    pub reward_mint: Box<Account<'info, Mint>>,
+    #[account(
+        seeds = [FARM_SEED.as_ref(), farm_pool.load()?.farm_number.to_le_bytes().as_ref()],
+        bump = farm_pool.load()?.bump,
+    )]
+    pub farm_pool: AccountLoader<'info, FarmData>,
 
-    #[account(mut)]
     /// CHECK: This is not dangerous because we don't read or write from this account
-    pub new_authority: AccountInfo<'info>,
-
-    pub token_program: Program<'info, Token>,
+    pub nft_mint: AccountInfo<'info>,
 }
 
+
 #[derive(Accounts)]
-pub struct MintToAccount<'info> {
-    #[account(
-        mut,
+#[instruction(bump: u8)]
+pub struct ClaimReward<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+ 
+    #[account(
+        mut,
+        seeds = [USER_POOL_SEED.as_ref(), owner.key().as_ref()],
+        bump = user_pool.bump,
+    )]
+    pub user_pool: Box<Account<'info, UserPool>>,
+
+    #[account(
+        mut,
+        seeds = [FARM_SEED.as_ref(), farm_pool.load()?.farm_number.to_le_bytes().as_ref()],
+        bump = farm_pool.load()?.bump,
+    )]
+    pub farm_pool: AccountLoader<'info, FarmData>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Box<Account<'info, GlobalPool>>,
+
+    #[account(
+        mut,
+        constraint = user_reward_account.mint == *reward_mint.key,
+        constraint = user_reward_account.owner == *owner.key,
+    )]
+    pub user_reward_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account(mut)]
+    pub reward_mint: AccountInfo<'info>,
+
+    /// CHECK: Only used and checked against the farm when the farm pays from a vault
+    #[account(mut)]
+    pub reward_vault: AccountInfo<'info>,
+
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub nft_mint: AccountInfo<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+
+#[derive(Accounts)]
+#[instruction(bump: u8)]
+pub struct ClaimAll<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+ 
+    #[account(
+        mut,
+        seeds = [USER_POOL_SEED.as_ref(), owner.key().as_ref()],
+        bump = user_pool.bump,
+    )]
+    pub user_pool: Box<Account<'info, UserPool>>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Box<Account<'info, GlobalPool>>,
+
+    #[account(
+        mut,
+        constraint = user_reward_account.mint == *reward_mint.key,
+        constraint = user_reward_account.owner == *owner.key,
+    )]
+    pub user_reward_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account(mut)]
+    pub reward_mint: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+
+#[derive(Accounts)]
+#[instruction(bump: u8)]
+pub struct StakeNftToReceipt<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = StakeReceipt::LEN,
+        seeds = [STAKE_RECEIPT_SEED.as_ref(), nft_mint.key().as_ref(), farm_pool.key().as_ref()],
+        bump,
+    )]
+    pub stake_receipt: Box<Account<'info, StakeReceipt>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = ReceiptCounter::LEN,
+        seeds = [RECEIPT_COUNTER_SEED.as_ref(), farm_pool.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub receipt_counter: Box<Account<'info, ReceiptCounter>>,
+
+    /// CHECK: Read when the owner has a user pool
+    #[account(
+        seeds = [USER_POOL_SEED.as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub user_pool: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [FARM_SEED.as_ref(), farm_pool.load()?.farm_number.to_le_bytes().as_ref()],
+        bump = farm_pool.load()?.bump,
+    )]
+    pub farm_pool: AccountLoader<'info, FarmData>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Box<Account<'info, GlobalPool>>,
+
+    #[account(
+        mut,
+        constraint = user_nft_token_account.mint == nft_mint.key(),
+        constraint = user_nft_token_account.owner == *owner.key,
+        constraint = user_nft_token_account.amount == 1,
+    )]
+    pub user_nft_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = dest_nft_token_account.mint == nft_mint.key(),
+        constraint = dest_nft_token_account.owner == global_authority.key(),
+    )]
+    pub dest_nft_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub nft_mint: AccountInfo<'info>,
+    /// the mint metadata
+    #[account(
+        mut,
+        constraint = mint_metadata.owner == &mpl_token_metadata::ID
+    )]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub mint_metadata: AccountInfo<'info>,
+    pub token_program: Program<'info, Token>,
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account(constraint = token_metadata_program.key == &mpl_token_metadata::ID)]
+    pub token_metadata_program: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+
+#[derive(Accounts)]
+pub struct BeginReceiptUnstake<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner @ StakingError::InvalidUserPool,
+        seeds = [STAKE_RECEIPT_SEED.as_ref(), nft_mint.key().as_ref(), farm_pool.key().as_ref()],
+        bump = stake_receipt.bump,
+    )]
+    pub stake_receipt: Box<Account<'info, StakeReceipt>>,
+
+    #[account(
+        seeds = [FARM_SEED.as_ref(), farm_pool.load()?.farm_number.to_le_bytes().as_ref()],
+        bump = farm_pool.load()?.bump,
+    )]
+    pub farm_pool: AccountLoader<'info, FarmData>,
+
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub nft_mint: AccountInfo<'info>,
+}
+
+
+#[derive(Accounts)]
+#[instruction(bump: u8)]
+pub struct UnstakeNftFromReceipt<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner @ StakingError::InvalidUserPool,
+        seeds = [STAKE_RECEIPT_SEED.as_ref(), nft_mint.key().as_ref(), farm_pool.key().as_ref()],
+        bump = stake_receipt.bump,
+    )]
+    pub stake_receipt: Box<Account<'info, StakeReceipt>>,
+
+    #[account(
+        mut,
+        seeds = [RECEIPT_COUNTER_SEED.as_ref(), farm_pool.key().as_ref(), owner.key().as_ref()],
+        bump = receipt_counter.bump,
+    )]
+    pub receipt_counter: Box<Account<'info, ReceiptCounter>>,
+
+    #[account(
+        mut,
+        seeds = [FARM_SEED.as_ref(), farm_pool.load()?.farm_number.to_le_bytes().as_ref()],
+        bump = farm_pool.load()?.bump,
+    )]
+    pub farm_pool: AccountLoader<'info, FarmData>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Box<Account<'info, GlobalPool>>,
+
+    #[account(
+        mut,
+        constraint = user_nft_token_account.mint == nft_mint.key(),
+        constraint = user_nft_token_account.owner == *owner.key,
+    )]
+    pub user_nft_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = dest_nft_token_account.mint == nft_mint.key(),
+        constraint = dest_nft_token_account.owner == global_authority.key(),
+        constraint = dest_nft_token_account.amount == 1,
+    )]
+    pub dest_nft_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_reward_account.mint == reward_mint.key(),
+        constraint = user_reward_account.owner == *owner.key,
+    )]
+    pub user_reward_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account(mut)]
+    pub reward_mint: AccountInfo<'info>,
+
+    /// CHECK: Only used and checked against the farm when the farm pays from a vault
+    #[account(mut)]
+    pub reward_vault: AccountInfo<'info>,
+
+    /// CHECK: Only used and checked against the farm when a penalty goes to its treasury
+    #[account(mut)]
+    pub penalty_treasury: AccountInfo<'info>,
+
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub nft_mint: AccountInfo<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+
+#[derive(Accounts)]
+#[instruction(bump: u8)]
+pub struct ClaimReceiptReward<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner @ StakingError::InvalidUserPool,
+        seeds = [STAKE_RECEIPT_SEED.as_ref(), nft_mint.key().as_ref(), farm_pool.key().as_ref()],
+        bump = stake_receipt.bump,
+    )]
+    pub stake_receipt: Box<Account<'info, StakeReceipt>>,
+
+    #[account(
+        seeds = [FARM_SEED.as_ref(), farm_pool.load()?.farm_number.to_le_bytes().as_ref()],
+        bump = farm_pool.load()?.bump,
+    )]
+    pub farm_pool: AccountLoader<'info, FarmData>,
+
+    #[account(
+        mut,
         seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
         bump,
     )]
     pub global_authority: Box<Account<'info, GlobalPool>>,
 
+    #[account(
+        mut,
+        constraint = user_reward_account.mint == *reward_mint.key,
+        constraint = user_reward_account.owner == *owner.key,
+    )]
+    pub user_reward_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account(mut)]
+    pub reward_mint: AccountInfo<'info>,
+
+    /// CHECK: Only used and checked against the farm when the farm pays from a vault
+    #[account(mut)]
+    pub reward_vault: AccountInfo<'info>,
+
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub nft_mint: AccountInfo<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+
+#[derive(Accounts)]
+pub struct MigrateToReceipt<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [USER_POOL_SEED.as_ref(), owner.key().as_ref()],
+        bump = user_pool.bump,
+    )]
+    pub user_pool: Box<Account<'info, UserPool>>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = StakeReceipt::LEN,
+        seeds = [STAKE_RECEIPT_SEED.as_ref(), nft_mint.key().as_ref(), farm_pool.key().as_ref()],
+        bump,
+    )]
+    pub stake_receipt: Box<Account<'info, StakeReceipt>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = ReceiptCounter::LEN,
+        seeds = [RECEIPT_COUNTER_SEED.as_ref(), farm_pool.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub receipt_counter: Box<Account<'info, ReceiptCounter>>,
+
+    #[account(
+        seeds = [FARM_SEED.as_ref(), farm_pool.load()?.farm_number.to_le_bytes().as_ref()],
+        bump = farm_pool.load()?.bump,
+    )]
+    pub farm_pool: AccountLoader<'info, FarmData>,
+
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub nft_mint: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+
+#[derive(Accounts)]
+pub struct SetUnstakePenalty<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Box<Account<'info, GlobalPool>>,
+
+    #[account(
+        seeds = [ADMIN_ROLES_SEED.as_ref()],
+        bump = admin_roles.bump,
+        constraint = admin_roles.allows(&global_authority.super_admin, &admin.key(), Role::FarmManager) @ StakingError::MissingRole,
+    )]
+    pub admin_roles: Box<Account<'info, AdminRoles>>,
+
+    #[account(
+        mut,
+        seeds = [FARM_SEED.as_ref(), farm_pool.load()?.farm_number.to_le_bytes().as_ref()],
+        bump = farm_pool.load()?.bump,
+    )]
+    pub farm_pool: AccountLoader<'info, FarmData>,
+}
+
+#[derive(Accounts)]
+pub struct InitRewardVault<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Box<Account<'info, GlobalPool>>,
+
+    #[account(
+        seeds = [ADMIN_ROLES_SEED.as_ref()],
+        bump = admin_roles.bump,
+        constraint = admin_roles.allows(&global_authority.super_admin, &admin.key(), Role::TreasuryManager) @ StakingError::MissingRole,
+    )]
+    pub admin_roles: Box<Account<'info, AdminRoles>>,
+
+    #[account(
+        mut,
+        seeds = [FARM_SEED.as_ref(), farm_pool.load()?.farm_number.to_le_bytes().as_ref()],
+        bump = farm_pool.load()?.bump,
+    )]
+    pub farm_pool: AccountLoader<'info, FarmData>,
+
+    pub reward_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        seeds = [REWARD_VAULT_SEED.as_ref(), farm_pool.key().as_ref()],
+        bump,
+        payer = admin,
+        token::mint = reward_mint,
+        token::authority = global_authority,
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>
+}
+
+#[derive(Accounts)]
+pub struct FundRewardVault<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Box<Account<'info, GlobalPool>>,
+
+    #[account(
+        seeds = [ADMIN_ROLES_SEED.as_ref()],
+        bump = admin_roles.bump,
+        constraint = admin_roles.allows(&global_authority.super_admin, &admin.key(), Role::TreasuryManager) @ StakingError::MissingRole,
+    )]
+    pub admin_roles: Box<Account<'info, AdminRoles>>,
+
+    #[account(
+        seeds = [FARM_SEED.as_ref(), farm_pool.load()?.farm_number.to_le_bytes().as_ref()],
+        bump = farm_pool.load()?.bump,
+    )]
+    pub farm_pool: AccountLoader<'info, FarmData>,
+
+    #[account(
+        mut,
+        constraint = admin_reward_account.owner == *admin.key,
+    )]
+    pub admin_reward_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct TransferAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+        constraint = global_authority.super_admin == admin.key() @ StakingError::InvalidSuperOwner,
+    )]
+    pub global_authority: Box<Account<'info, GlobalPool>>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub reward_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub new_authority: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct MintToAccount<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_authority: Box<Account<'info, GlobalPool>>,
+
+    #[account(
+        seeds = [ADMIN_ROLES_SEED.as_ref()],
+        bump = admin_roles.bump,
+        constraint = admin_roles.allows(&global_authority.super_admin, &admin.key(), Role::TreasuryManager) @ StakingError::MissingRole,
+    )]
+    pub admin_roles: Box<Account<'info, AdminRoles>>,
+
     #[account(mut)]
     pub admin: Signer<'info>,
 
@@ -714,8 +2055,406 @@ pub struct MintToAccount<'info> {
 }
 
 // Access control modifiers
-fn user(pool_loader: &AccountLoader<UserPool>, user: &AccountInfo) -> Result<()> {
-    let user_pool = pool_loader.load()?;
+fn user(user_pool: &Account<UserPool>, user: &AccountInfo) -> Result<()> {
     require!(user_pool.owner == *user.key, StakingError::InvalidUserPool);
     Ok(())
-}
\ No newline at end of file
+}
+
+// Checks that the NFT is allowed in the farm and returns its reward multiplier.
+// The rules are evaluated in this order:
+// 1. a deny entry of the mint rejects it, clients always pass its address among the remaining accounts
+//    so that leaving it out can't skip the check
+// 2. a merkle proof against the farm's merkle root allows it at the farm's rate
+// 3. a mint entry, an entry of an update authority that is a verified creator, or a collection entry
+//    of the verified collection or of a verified creator allows it at the entry's multiplier
+// The other remaining accounts are allow entries, clients only pass the entry they use.
+// Merkle and mint entries don't need the metadata to have a collection or creators.
+fn verify_whitelist(
+    nft_mint: &AccountInfo,
+    mint_metadata: &AccountInfo,
+    farm_pool: &Pubkey,
+    merkle_root: &[u8; 32],
+    merkle_proof: Option<&[[u8; 32]]>,
+    remaining_accounts: &[AccountInfo],
+) -> Result<u64> {
+    msg!("Metadata Account: {:?}", mint_metadata.key());
+    let (metadata, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            nft_mint.key().as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+    require!(metadata == mint_metadata.key(), StakingError::InvaliedMetadata);
+
+    let wl_deny_address = whitelist_address(nft_mint.key, farm_pool, WhitelistKind::Deny);
+    let deny_entry = remaining_accounts.iter()
+        .find(|proof| *proof.key == wl_deny_address)
+        .ok_or(StakingError::MissingDenyAccount)?;
+    // Only this program can give data to the address, so any data is a deny entry
+    require!(deny_entry.data_is_empty(), StakingError::DeniedNFT);
+
+    if let Some(merkle_proof) = merkle_proof {
+        require!(*merkle_root != [0; 32], StakingError::InvalidMerkleProof);
+        require!(merkle::verify_proof(merkle_root, nft_mint.key, merkle_proof), StakingError::InvalidMerkleProof);
+        return Ok(BPS_DENOMINATOR);
+    }
+
+    if let Some(multiplier_bps) = whitelist_multiplier(remaining_accounts, nft_mint.key, farm_pool, WhitelistKind::Mint)? {
+        return Ok(multiplier_bps);
+    }
+
+    // verify metadata is legit
+    let nft_metadata = Metadata::from_account_info(mint_metadata)?;
+    msg!("Update Authority= {:?}", nft_metadata.update_authority);
+    // Metadata can name any update authority, it only counts when it also signed as a verified creator
+    let update_authority_verified = nft_metadata.data.creators.iter().flatten()
+        .any(|creator| creator.verified && creator.address == nft_metadata.update_authority);
+    if update_authority_verified {
+        if let Some(multiplier_bps) = whitelist_multiplier(remaining_accounts, &nft_metadata.update_authority, farm_pool, WhitelistKind::UpdateAuthority)? {
+            return Ok(multiplier_bps);
+        }
+    }
+
+    let collections = verified_collections(&nft_metadata);
+    require!(!collections.is_empty(), StakingError::UnverifiedCollection);
+    for collection in collections.iter() {
+        msg!("Collection= {:?}", collection);
+        if let Some(multiplier_bps) = whitelist_multiplier(remaining_accounts, collection, farm_pool, WhitelistKind::Collection)? {
+            return Ok(multiplier_bps);
+        }
+    }
+    Err(error!(StakingError::InvalidWhitelistAddress))
+}
+
+// Reward weight of the NFT, farms without a rarity root weigh every NFT the same
+fn verify_rarity(
+    nft_mint: &Pubkey,
+    rarity_root: &[u8; 32],
+    rarity: Option<&RarityProof>,
+) -> Result<u64> {
+    if *rarity_root == [0; 32] {
+        return Ok(BPS_DENOMINATOR);
+    }
+    let rarity = rarity.ok_or(StakingError::InvalidRarityProof)?;
+    require!(merkle::verify_rarity_proof(rarity_root, nft_mint, rarity.weight_bps, &rarity.proof),
+        StakingError::InvalidRarityProof);
+    require!(rarity.weight_bps > 0 && rarity.weight_bps <= MAX_RARITY_WEIGHT_BPS,
+        StakingError::InvalidRarityWeight);
+    Ok(rarity.weight_bps)
+}
+
+fn whitelist_address(
+    address: &Pubkey,
+    farm_pool: &Pubkey,
+    kind: WhitelistKind,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            address.as_ref(),
+            farm_pool.as_ref(),
+            kind.seed(),
+        ],
+        &crate::ID,
+    ).0
+}
+
+// Address of allow entries stored before the kind seeds, their stored kind tells them apart
+fn legacy_whitelist_address(
+    address: &Pubkey,
+    farm_pool: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(&[address.as_ref(), farm_pool.as_ref()], &crate::ID).0
+}
+
+// Reward multiplier of the whitelist entry of `kind` for `address`, none if the entry isn't passed or doesn't exist.
+// An account at the entry's address that doesn't hold an entry is an error rather than a missing entry.
+fn whitelist_multiplier(
+    proofs: &[AccountInfo],
+    address: &Pubkey,
+    farm_pool: &Pubkey,
+    kind: WhitelistKind,
+) -> Result<Option<u64>> {
+    let proof_address = whitelist_address(address, farm_pool, kind);
+    let legacy_address = legacy_whitelist_address(address, farm_pool);
+    for proof in proofs.iter().filter(|proof| *proof.key == proof_address || *proof.key == legacy_address) {
+        if proof.data_is_empty() {
+            continue;
+        }
+        let entry = WhitelistProof::load(proof)?;
+        if entry.kind == kind {
+            return Ok(Some(entry.multiplier_bps));
+        }
+    }
+    Ok(None)
+}
+
+// Addresses a collection entry can match: the verified Metaplex collection first, then every verified creator
+fn verified_collections(metadata: &Metadata) -> Vec<Pubkey> {
+    let collection = metadata.collection.as_ref()
+        .filter(|collection| collection.verified)
+        .map(|collection| collection.key);
+    let creators = metadata.data.creators.iter().flatten()
+        .filter(|creator| creator.verified)
+        .map(|creator| creator.address);
+    collection.into_iter().chain(creators).collect()
+}
+
+// Withdraws a staked NFT and pays its reward, `finalize` completes a requested unstake after the cooldown
+fn unstake_nft(
+    ctx: Context<UnstakeNftFromPool>,
+    global_bump: u8,
+    finalize: bool,
+) -> Result<()> {
+    let user_pool = &mut ctx.accounts.user_pool;
+    msg!("Staked Mint: {:?}", ctx.accounts.nft_mint.key());
+    let mut farm_pool = ctx.accounts.farm_pool.load_mut()?;
+    require!(farm_pool.reward_mint == ctx.accounts.reward_mint.key(), StakingError::InvalidRewardMint);
+    let global_authority = &mut ctx.accounts.global_authority;
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    let (staked_data, reward) = user_pool.remove_nft(ctx.accounts.nft_mint.key(), ctx.accounts.farm_pool.key(), &farm_pool, timestamp)?;
+    if finalize {
+        farm_pool.check_cooldown(&staked_data, timestamp)?;
+    }
+    msg!("Reward: {:?} Unstaked Time: {}", reward, timestamp);
+    global_authority.total_staked_count -= 1;
+    farm_pool.staked_count -= 1;
+
+    let seeds = &[GLOBAL_AUTHORITY_SEED.as_bytes(), &[global_bump]];
+    let signer = &[&seeds[..]];
+
+    withdraw_nft(
+        &ctx.accounts.dest_nft_token_account,
+        &ctx.accounts.user_nft_token_account,
+        &ctx.accounts.owner,
+        &global_authority.to_account_info(),
+        &ctx.accounts.token_program,
+        signer,
+    )?;
+    let (penalty, unpaid_reward) = pay_unstake_reward(
+        &farm_pool,
+        &staked_data,
+        reward,
+        timestamp,
+        &ctx.accounts.reward_vault,
+        &ctx.accounts.reward_mint,
+        &ctx.accounts.user_reward_account.to_account_info(),
+        &ctx.accounts.penalty_treasury,
+        &global_authority.to_account_info(),
+        &ctx.accounts.token_program,
+        signer,
+    )?;
+
+    emit!(UnstakeEvent {
+        farm_number: farm_pool.farm_number,
+        mint: ctx.accounts.nft_mint.key(),
+        owner: ctx.accounts.owner.key(),
+        reward,
+        penalty,
+        unpaid_reward,
+        timestamp,
+    });
+    Ok(())
+}
+
+// Returns a staked NFT to its owner and closes the program's token account
+fn withdraw_nft<'info>(
+    dest_nft_token_account: &Account<'info, TokenAccount>,
+    user_nft_token_account: &Account<'info, TokenAccount>,
+    owner: &Signer<'info>,
+    global_authority: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+    signer: &[&[&[u8]]],
+) -> Result<()> {
+    let cpi_accounts = Transfer {
+        from: dest_nft_token_account.to_account_info(),
+        to: user_nft_token_account.to_account_info(),
+        authority: global_authority.clone(),
+    };
+    token::transfer(
+        CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer),
+        1
+    )?;
+
+    invoke_signed(
+        &spl_token::instruction::close_account(
+            token_program.key,
+            &dest_nft_token_account.key(),
+            owner.key,
+            global_authority.key,
+            &[],
+        )?,
+        &[
+            token_program.to_account_info(),
+            dest_nft_token_account.to_account_info(),
+            owner.to_account_info(),
+            global_authority.clone(),
+        ],
+        signer,
+    )?;
+    Ok(())
+}
+
+// Pays the reward of an unstaked NFT minus its early unstake penalty, returns the penalty and the unpaid reward.
+// The NFT is withdrawn even when the farm's vault can't pay, the vault then pays what it holds
+// and the rest of the reward is left unpaid instead of failing. The treasury's penalty is only paid after the owner.
+#[allow(clippy::too_many_arguments)]
+fn pay_unstake_reward<'info>(
+    farm_pool: &FarmData,
+    staked_data: &StakedData,
+    reward: u64,
+    timestamp: i64,
+    reward_vault: &AccountInfo<'info>,
+    reward_mint: &AccountInfo<'info>,
+    user_reward_account: &AccountInfo<'info>,
+    penalty_treasury: &AccountInfo<'info>,
+    global_authority: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+    signer: &[&[&[u8]]],
+) -> Result<(u64, u64)> {
+    // Stopped farms don't emit rewards, the whole reward is reported as unpaid
+    if farm_pool.is_stop {
+        msg!("Farm {} is stopped, reward {} is not paid", farm_pool.farm_number, reward);
+        return Ok((0, reward));
+    }
+
+    let penalty = farm_pool.early_unstake_penalty(staked_data.staked_time, staked_data.accrual_end(timestamp), reward)?;
+    if penalty > 0 {
+        msg!("Early Unstake Penalty: {:?}", penalty);
+    }
+    let available = vault_balance(farm_pool, reward_vault)?;
+    let owed = reward - penalty;
+    let paid = owed.min(available);
+    if paid < owed {
+        msg!("Reward vault can't pay the reward, {} is not paid", owed - paid);
+    }
+    if paid > 0 {
+        pay_reward(
+            farm_pool,
+            reward_vault,
+            reward_mint,
+            user_reward_account,
+            global_authority,
+            &token_program.to_account_info(),
+            signer,
+            paid,
+        )?;
+    }
+    // The forfeited reward goes to the treasury if the farm has one, otherwise it isn't paid
+    let treasury_penalty = penalty.min(available - paid);
+    if treasury_penalty > 0 && farm_pool.penalty_treasury != Pubkey::default() {
+        require!(penalty_treasury.key() == farm_pool.penalty_treasury,
+            StakingError::InvalidPenaltyTreasury);
+        pay_reward(
+            farm_pool,
+            reward_vault,
+            reward_mint,
+            penalty_treasury,
+            global_authority,
+            &token_program.to_account_info(),
+            signer,
+            treasury_penalty,
+        )?;
+    }
+    Ok((penalty, owed - paid))
+}
+
+// NFTs of the user pool staked in the farm
+fn farm_stake_count(
+    user_pool: &UserPool,
+    farm_pool: &Pubkey,
+) -> u64 {
+    user_pool.staking.iter()
+        .filter(|staked_data| staked_data.farm == *farm_pool)
+        .count() as u64
+}
+
+// Loads a program account which may not have been created yet
+fn load_initialized<T: AccountDeserialize>(info: &AccountInfo) -> Result<Option<T>> {
+    if info.data_is_empty() {
+        return Ok(None);
+    }
+    require!(*info.owner == crate::ID, StakingError::InvalidInput);
+    Ok(Some(T::try_deserialize(&mut &info.try_borrow_data()?[..])?))
+}
+
+// Amount the farm can pay, farms without a vault mint their rewards
+fn vault_balance(
+    farm_pool: &FarmData,
+    reward_vault: &AccountInfo,
+) -> Result<u64> {
+    if farm_pool.reward_vault == Pubkey::default() {
+        return Ok(u64::MAX);
+    }
+    require!(reward_vault.key() == farm_pool.reward_vault, StakingError::InvalidRewardVault);
+    let vault = TokenAccount::try_deserialize(&mut &reward_vault.try_borrow_data()?[..])?;
+    Ok(vault.amount)
+}
+
+// Reallocs the user pool to fit its staked NFTs, the owner pays the extra rent
+fn grow_user_pool<'info>(
+    user_pool: &Account<'info, UserPool>,
+    owner: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let user_pool_info = user_pool.to_account_info();
+    let space = UserPool::space(user_pool.staking.len());
+    if user_pool_info.data_len() >= space {
+        return Ok(());
+    }
+
+    let rent = Rent::get()?.minimum_balance(space).saturating_sub(user_pool_info.lamports());
+    if rent > 0 {
+        let cpi_accounts = system_program::Transfer {
+            from: owner.to_account_info(),
+            to: user_pool_info.clone(),
+        };
+        system_program::transfer(
+            CpiContext::new(system_program.to_account_info(), cpi_accounts),
+            rent
+        )?;
+    }
+    user_pool_info.realloc(space, false)?;
+    Ok(())
+}
+
+// Pays a reward out of the farm's vault, or mints it when the farm has no vault
+#[allow(clippy::too_many_arguments)]
+fn pay_reward<'info>(
+    farm_pool: &FarmData,
+    reward_vault: &AccountInfo<'info>,
+    reward_mint: &AccountInfo<'info>,
+    user_reward_account: &AccountInfo<'info>,
+    global_authority: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    signer: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    if farm_pool.reward_vault == Pubkey::default() {
+        let cpi_accounts = MintTo {
+            mint: reward_mint.clone(),
+            to: user_reward_account.clone(),
+            authority: global_authority.clone(),
+        };
+        return token::mint_to(
+            CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer),
+            amount
+        );
+    }
+
+    require!(reward_vault.key() == farm_pool.reward_vault, StakingError::InvalidRewardVault);
+    let vault = Account::<TokenAccount>::try_from(reward_vault)?;
+    require!(vault.amount >= amount, StakingError::InsufficientRewardVault);
+
+    let cpi_accounts = Transfer {
+        from: reward_vault.clone(),
+        to: user_reward_account.clone(),
+        authority: global_authority.clone(),
+    };
+    token::transfer(
+        CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer),
+        amount
+    )
+}
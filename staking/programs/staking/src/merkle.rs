@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+// Leaves and inner nodes are hashed with different prefixes,
+// so an inner node can't be passed off as a leaf.
+// Rarity leaves have their own prefix, an allowlist root can't prove a weight.
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+const RARITY_LEAF_PREFIX: &[u8] = &[2];
+
+/// Hash of an allowed mint in a farm's merkle tree
+pub fn leaf_hash(mint: &Pubkey) -> [u8; 32] {
+    keccak::hashv(&[LEAF_PREFIX, mint.as_ref()]).0
+}
+
+/// Hash of a mint and its reward weight in a farm's rarity tree
+pub fn rarity_leaf_hash(
+    mint: &Pubkey,
+    weight_bps: u64,
+) -> [u8; 32] {
+    keccak::hashv(&[RARITY_LEAF_PREFIX, mint.as_ref(), &weight_bps.to_le_bytes()]).0
+}
+
+/// Hash of two sibling nodes, sorted so proofs don't need to carry the sibling's side
+pub fn node_hash(
+    a: &[u8; 32],
+    b: &[u8; 32],
+) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    keccak::hashv(&[NODE_PREFIX, left.as_ref(), right.as_ref()]).0
+}
+
+/// Whether `proof` leads from the leaf of `mint` up to `root`
+pub fn verify_proof(
+    root: &[u8; 32],
+    mint: &Pubkey,
+    proof: &[[u8; 32]],
+) -> bool {
+    verify_leaf(root, leaf_hash(mint), proof)
+}
+
+/// Whether `proof` leads from the rarity leaf of `mint` and `weight_bps` up to `root`
+pub fn verify_rarity_proof(
+    root: &[u8; 32],
+    mint: &Pubkey,
+    weight_bps: u64,
+    proof: &[[u8; 32]],
+) -> bool {
+    verify_leaf(root, rarity_leaf_hash(mint, weight_bps), proof)
+}
+
+fn verify_leaf(
+    root: &[u8; 32],
+    leaf: [u8; 32],
+    proof: &[[u8; 32]],
+) -> bool {
+    proof.iter().fold(leaf, |node, sibling| node_hash(&node, sibling)) == *root
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::*;
+
+/// Checks a tier schedule before it's stored in a farm.
+/// The last tier is open-ended, so its duration may be zero.
+pub fn validate_tiers(
+    tier_duration: &[i64],
+    tier_rate: &[u64],
+) -> Result<()> {
+    require!(!tier_rate.is_empty() && tier_rate.len() <= MAX_TIER_COUNT, StakingError::InvalidTierCount);
+    require!(tier_duration.len() == tier_rate.len(), StakingError::TierLengthMismatch);
+
+    let last = tier_rate.len() - 1;
+    require!(tier_duration[..last].iter().all(|duration| *duration > 0) && tier_duration[last] >= 0,
+        StakingError::InvalidTierDuration);
+    require!(tier_rate.iter().all(|rate| *rate <= MAX_TIER_RATE), StakingError::TierRateTooHigh);
+    Ok(())
+}
+
+/// Reward earned by staking for `duration` seconds under a tier schedule.
+/// Every tier but the last one lasts `tier_duration[i]` seconds,
+/// the last tier's rate applies for the rest of the duration.
+pub fn accrued_reward(
+    tier_duration: &[i64],
+    tier_rate: &[u64],
+    duration: i64,
+) -> Result<u64> {
+    require!(!tier_rate.is_empty() && tier_duration.len() == tier_rate.len(), StakingError::InvalidInput);
+
+    let last = tier_rate.len() - 1;
+    let mut remaining: u64 = if duration > 0 { duration as u64 } else { 0 };
+    let mut reward: u64 = 0;
+    for (tier_length, rate) in tier_duration.iter().zip(tier_rate.iter()).take(last) {
+        let tier_time = remaining.min(if *tier_length > 0 { *tier_length as u64 } else { 0 });
+        reward = tier_time.checked_mul(*rate)
+            .and_then(|tier_reward| reward.checked_add(tier_reward))
+            .ok_or(StakingError::RewardOverflow)?;
+        remaining -= tier_time;
+        if remaining == 0 {
+            return Ok(reward);
+        }
+    }
+    reward = remaining.checked_mul(tier_rate[last])
+        .and_then(|tier_reward| reward.checked_add(tier_reward))
+        .ok_or(StakingError::RewardOverflow)?;
+    Ok(reward)
+}
+
+/// Scales a reward by a multiplier in basis points
+pub fn apply_multiplier(
+    reward: u64,
+    multiplier_bps: u64,
+) -> Result<u64> {
+    let reward = (reward as u128) * (multiplier_bps as u128) / (BPS_DENOMINATOR as u128);
+    u64::try_from(reward).map_err(|_| error!(StakingError::RewardOverflow))
+}
+
+/// Unclaimed reward of an NFT staked at `staked_time` and last claimed at `claimed_time`.
+/// Tiers are counted from `staked_time`, so a claim doesn't reset the NFT to the first tier.
+pub fn calculate_reward(
+    tier_duration: &[i64],
+    tier_rate: &[u64],
+    staked_time: i64,
+    claimed_time: i64,
+    now: i64,
+) -> Result<u64> {
+    // The clock can lag behind a recorded timestamp, nothing is accrued then
+    if now <= claimed_time {
+        return Ok(0);
+    }
+    let total_duration = now.checked_sub(staked_time).ok_or(StakingError::RewardOverflow)?;
+    let claimed_duration = claimed_time.checked_sub(staked_time).ok_or(StakingError::RewardOverflow)?;
+
+    let reward = accrued_reward(tier_duration, tier_rate, total_duration)?;
+    let claimed_reward = accrued_reward(tier_duration, tier_rate, claimed_duration)?;
+    reward.checked_sub(claimed_reward).ok_or_else(|| error!(StakingError::RewardOverflow))
+}
@@ -0,0 +1,413 @@
+//! Every administrative instruction has to reject a signer other than the super admin
+//! or an operator granted the instruction's role.
+//! Instructions run through `staking::entry` with the shared test harness.
+
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{entrypoint::ProgramResult, program_pack::Pack, system_program};
+use anchor_lang::{AccountDeserialize, InstructionData};
+use common::harness::*;
+use common::rejection;
+use spl_token::state::Account as SplTokenAccount;
+use staking::account::{AdminProposal, AdminRoles, FarmData, GlobalPool, Role, RoleGrant, WhitelistKind, WhitelistProof};
+use staking::constants::*;
+use staking::error::StakingError;
+
+/// A program state with one farm, whose super admin is `admin`.
+/// Each operator is granted one role, and the super admin is proposed to `proposed_admin`.
+struct Fixture {
+    admin: Pubkey,
+    proposed_admin: Pubkey,
+    operators: [(Pubkey, Role); 3],
+    global_authority: Pubkey,
+    farm_pool: Pubkey,
+    // Farm created by create_farm
+    new_farm_pool: Pubkey,
+    reward_mint: Pubkey,
+    reward_vault: Pubkey,
+    whitelist_address: Pubkey,
+}
+
+impl Fixture {
+    fn new() -> Self {
+        let farm_pool = pda(&[FARM_SEED.as_ref(), 1u64.to_le_bytes().as_ref()]);
+        Fixture {
+            admin: Pubkey::new_unique(),
+            proposed_admin: Pubkey::new_unique(),
+            operators: [
+                (Pubkey::new_unique(), Role::FarmManager),
+                (Pubkey::new_unique(), Role::WhitelistManager),
+                (Pubkey::new_unique(), Role::TreasuryManager),
+            ],
+            global_authority: pda(&[GLOBAL_AUTHORITY_SEED.as_ref()]),
+            farm_pool,
+            new_farm_pool: pda(&[FARM_SEED.as_ref(), 2u64.to_le_bytes().as_ref()]),
+            reward_mint: Pubkey::new_unique(),
+            reward_vault: pda(&[REWARD_VAULT_SEED.as_ref(), farm_pool.as_ref()]),
+            whitelist_address: Pubkey::new_unique(),
+        }
+    }
+
+    fn global_authority(&self) -> TestAccount {
+        program_account(self.global_authority, &GlobalPool {
+            super_admin: self.admin,
+            reward_token: self.reward_mint,
+            total_staked_count: 0,
+            farm_count: 1,
+        })
+    }
+
+    fn admin_roles(&self) -> TestAccount {
+        let mut account = program_account(admin_roles_address(), &AdminRoles {
+            bump: Pubkey::find_program_address(&[ADMIN_ROLES_SEED.as_ref()], &staking::ID).1,
+            grants: self.operators.iter()
+                .map(|(operator, role)| RoleGrant { operator: *operator, role: *role })
+                .collect(),
+        });
+        // The account is created with room for every grant
+        account.data.resize(AdminRoles::LEN, 0);
+        account
+    }
+
+    fn admin_proposal(&self) -> TestAccount {
+        program_account(admin_proposal_address(), &AdminProposal {
+            proposed_admin: self.proposed_admin,
+            proposed_time: NOW,
+        })
+    }
+
+    fn farm_pool(&self) -> TestAccount {
+        let mut farm = FarmData {
+            farm_number: 1,
+            max_stake_count: 10,
+            reward_mint: self.reward_mint,
+            reward_vault: self.reward_vault,
+            bump: Pubkey::find_program_address(&[FARM_SEED.as_ref(), 1u64.to_le_bytes().as_ref()], &staking::ID).1,
+            ..FarmData::default()
+        };
+        farm.set_tiers(&[0], &[1]).unwrap();
+        farm_account(self.farm_pool, &farm)
+    }
+
+    fn whitelist_proof(&self) -> Pubkey {
+        pda(&[self.whitelist_address.as_ref(), self.farm_pool.as_ref(), WhitelistKind::Collection.seed()])
+    }
+
+    fn reward_mint(&self) -> TestAccount {
+        mint_account(self.reward_mint, self.global_authority)
+    }
+
+    fn run(&self, instruction: &str, signer: Pubkey) -> ProgramResult {
+        self.execute(instruction, signer).0
+    }
+
+    // Runs the instruction and returns the accounts it was given, with its changes
+    fn execute(&self, instruction: &str, signer: Pubkey) -> (ProgramResult, Vec<TestAccount>) {
+        let (accounts, data) = match instruction {
+            "propose_admin" => (vec![
+                TestAccount::signer(signer),
+                self.global_authority(),
+                TestAccount::uninitialized(admin_proposal_address(), 8 + 40),
+                TestAccount::program(system_program::ID),
+            ], staking::instruction::ProposeAdmin {
+                new_admin: Pubkey::new_unique(),
+            }.data()),
+            "cancel_admin_proposal" => (vec![
+                TestAccount::signer(signer),
+                self.global_authority(),
+                self.admin_proposal(),
+            ], staking::instruction::CancelAdminProposal {}.data()),
+            "accept_admin" => (vec![
+                TestAccount::signer(signer),
+                self.global_authority(),
+                self.admin_proposal(),
+            ], staking::instruction::AcceptAdmin {}.data()),
+            "init_admin_roles" => (vec![
+                TestAccount::signer(signer),
+                self.global_authority(),
+                TestAccount::uninitialized(admin_roles_address(), AdminRoles::LEN),
+                TestAccount::program(system_program::ID),
+            ], staking::instruction::InitAdminRoles {}.data()),
+            "grant_role" => (vec![
+                TestAccount::signer(signer),
+                self.global_authority(),
+                self.admin_roles(),
+            ], staking::instruction::GrantRole {
+                operator: Pubkey::new_unique(),
+                role: Role::FarmManager,
+            }.data()),
+            "revoke_role" => (vec![
+                TestAccount::signer(signer),
+                self.global_authority(),
+                self.admin_roles(),
+            ], staking::instruction::RevokeRole {
+                operator: self.operators[0].0,
+                role: self.operators[0].1,
+            }.data()),
+            "create_farm" => (vec![
+                TestAccount::signer(signer),
+                self.global_authority(),
+                self.admin_roles(),
+                TestAccount::uninitialized(self.new_farm_pool, 8 + std::mem::size_of::<FarmData>()),
+                self.reward_mint(),
+                TestAccount::program(system_program::ID),
+            ], staking::instruction::CreateFarm {
+                tier_duration: vec![0],
+                tier_rate: vec![1],
+                max_stake_count: 10,
+                min_lock_duration: 0,
+                cooldown_duration: 0,
+                count: 2,
+            }.data()),
+            "update_farm" => (vec![
+                TestAccount::signer(signer),
+                self.global_authority(),
+                self.admin_roles(),
+                self.farm_pool(),
+            ], staking::instruction::UpdateFarm {
+                new_tier_duration: vec![0],
+                new_tier_rate: vec![2],
+                max_stake_count: 10,
+                min_lock_duration: 0,
+                cooldown_duration: 0,
+            }.data()),
+            "add_whitelist" => (vec![
+                TestAccount::signer(signer),
+                self.global_authority(),
+                self.admin_roles(),
+                self.farm_pool(),
+                TestAccount::new(self.whitelist_address, system_program::ID, vec![]),
+                TestAccount::uninitialized(self.whitelist_proof(), 8 + 41),
+                TestAccount::program(system_program::ID),
+                rent_sysvar(),
+            ], staking::instruction::AddWhitelist {
+                kind: WhitelistKind::Collection,
+                multiplier_bps: 10_000,
+            }.data()),
+            "remove_whitelist" => (vec![
+                TestAccount::signer(signer),
+                self.global_authority(),
+                self.admin_roles(),
+                self.farm_pool(),
+                TestAccount::new(self.whitelist_address, system_program::ID, vec![]),
+                program_account(self.whitelist_proof(), &WhitelistProof {
+                    whitelist_address: self.whitelist_address,
+                    kind: WhitelistKind::Collection,
+                    multiplier_bps: 10_000,
+                }),
+            ], staking::instruction::RemoveWhitelist {}.data()),
+            // Entry stored at the address it had before the kind seeds
+            "remove_legacy_whitelist" => (vec![
+                TestAccount::signer(signer),
+                self.global_authority(),
+                self.admin_roles(),
+                self.farm_pool(),
+                TestAccount::new(self.whitelist_address, system_program::ID, vec![]),
+                program_account(pda(&[self.whitelist_address.as_ref(), self.farm_pool.as_ref()]), &WhitelistProof {
+                    whitelist_address: self.whitelist_address,
+                    kind: WhitelistKind::Collection,
+                    multiplier_bps: 10_000,
+                }),
+            ], staking::instruction::RemoveWhitelist {}.data()),
+            "set_stop" => (vec![
+                TestAccount::signer(signer),
+                self.global_authority(),
+                self.admin_roles(),
+                self.farm_pool(),
+            ], staking::instruction::SetStop {
+                is_stop: true,
+            }.data()),
+            "set_merkle_root" => (vec![
+                TestAccount::signer(signer),
+                self.global_authority(),
+                self.admin_roles(),
+                self.farm_pool(),
+            ], staking::instruction::SetMerkleRoot {
+                merkle_root: [1; 32],
+            }.data()),
+            "set_rarity_root" => (vec![
+                TestAccount::signer(signer),
+                self.global_authority(),
+                self.admin_roles(),
+                self.farm_pool(),
+            ], staking::instruction::SetRarityRoot {
+                rarity_root: [1; 32],
+            }.data()),
+            "set_unstake_penalty" => (vec![
+                TestAccount::signer(signer),
+                self.global_authority(),
+                self.admin_roles(),
+                self.farm_pool(),
+            ], staking::instruction::SetUnstakePenalty {
+                penalty_duration: 100,
+                penalty_bps: 5_000,
+                penalty_treasury: Some(signer),
+            }.data()),
+            "init_reward_vault" => (vec![
+                TestAccount::signer(signer),
+                self.global_authority(),
+                self.admin_roles(),
+                self.farm_pool(),
+                self.reward_mint(),
+                TestAccount::uninitialized(self.reward_vault, SplTokenAccount::LEN),
+                TestAccount::program(spl_token::ID),
+                TestAccount::program(system_program::ID),
+                rent_sysvar(),
+            ], staking::instruction::InitRewardVault {}.data()),
+            "fund_reward_vault" => (vec![
+                TestAccount::signer(signer),
+                self.global_authority(),
+                self.admin_roles(),
+                self.farm_pool(),
+                token_account(Pubkey::new_unique(), self.reward_mint, signer, 1_000),
+                token_account(self.reward_vault, self.reward_mint, self.global_authority, 1_000),
+                TestAccount::program(spl_token::ID),
+            ], staking::instruction::FundRewardVault {
+                amount: 100,
+            }.data()),
+            "transfer_mint_authority" => (vec![
+                self.global_authority(),
+                TestAccount::signer(signer),
+                self.reward_mint(),
+                TestAccount::new(signer, system_program::ID, vec![]),
+                TestAccount::program(spl_token::ID),
+            ], staking::instruction::TransferMintAuthority {
+                global_bump: 0,
+            }.data()),
+            "mint_to_account" => (vec![
+                self.global_authority(),
+                self.admin_roles(),
+                TestAccount::signer(signer),
+                self.reward_mint(),
+                token_account(Pubkey::new_unique(), self.reward_mint, signer, 1_000),
+                TestAccount::program(spl_token::ID),
+            ], staking::instruction::MintToAccount {
+                global_bump: 0,
+                amount: 100,
+            }.data()),
+            _ => unreachable!("unknown instruction {}", instruction),
+        };
+        execute(accounts, data)
+    }
+}
+
+fn admin_proposal_address() -> Pubkey {
+    pda(&[ADMIN_PROPOSAL_SEED.as_ref()])
+}
+
+fn admin_roles_address() -> Pubkey {
+    pda(&[ADMIN_ROLES_SEED.as_ref()])
+}
+
+// Role an operator needs for each instruction, none when only the super admin may run it
+const ADMIN_INSTRUCTIONS: [(&str, Option<Role>); 17] = [
+    ("propose_admin", None),
+    ("cancel_admin_proposal", None),
+    ("init_admin_roles", None),
+    ("grant_role", None),
+    ("revoke_role", None),
+    ("create_farm", Some(Role::FarmManager)),
+    ("update_farm", Some(Role::FarmManager)),
+    ("add_whitelist", Some(Role::WhitelistManager)),
+    ("remove_whitelist", Some(Role::WhitelistManager)),
+    ("set_stop", Some(Role::FarmManager)),
+    ("set_merkle_root", Some(Role::WhitelistManager)),
+    ("set_rarity_root", Some(Role::FarmManager)),
+    ("set_unstake_penalty", Some(Role::FarmManager)),
+    ("init_reward_vault", Some(Role::TreasuryManager)),
+    ("fund_reward_vault", Some(Role::TreasuryManager)),
+    ("transfer_mint_authority", None),
+    ("mint_to_account", Some(Role::TreasuryManager)),
+];
+
+// Error of a signer missing the instruction's role
+fn missing_role(role: Option<Role>) -> StakingError {
+    match role {
+        Some(_) => StakingError::MissingRole,
+        None => StakingError::InvalidSuperOwner,
+    }
+}
+
+#[test]
+fn super_admin_passes_every_admin_instruction() {
+    set_stubs();
+    let fixture = Fixture::new();
+    for (instruction, _) in ADMIN_INSTRUCTIONS {
+        assert_eq!(fixture.run(instruction, fixture.admin), Ok(()), "{}", instruction);
+    }
+}
+
+#[test]
+fn operators_only_pass_the_instructions_of_their_role() {
+    set_stubs();
+    let fixture = Fixture::new();
+    for (operator, operator_role) in fixture.operators {
+        for (instruction, role) in ADMIN_INSTRUCTIONS {
+            let expected = if role == Some(operator_role) { Ok(()) } else { rejection(missing_role(role)) };
+            assert_eq!(fixture.run(instruction, operator), expected, "{:?} {}", operator_role, instruction);
+        }
+    }
+}
+
+#[test]
+fn other_signers_are_rejected_by_every_admin_instruction() {
+    set_stubs();
+    let fixture = Fixture::new();
+    for (instruction, role) in ADMIN_INSTRUCTIONS {
+        assert_eq!(fixture.run(instruction, Pubkey::new_unique()), rejection(missing_role(role)), "{}", instruction);
+    }
+}
+
+#[test]
+fn only_the_proposed_admin_accepts_the_handover() {
+    set_stubs();
+    let fixture = Fixture::new();
+    for signer in [fixture.admin, Pubkey::new_unique()] {
+        assert_eq!(
+            fixture.run("accept_admin", signer),
+            rejection(StakingError::InvalidProposedAdmin),
+        );
+    }
+
+    let (result, accounts) = fixture.execute("accept_admin", fixture.proposed_admin);
+    assert_eq!(result, Ok(()));
+    let global_authority = GlobalPool::try_deserialize(&mut accounts[1].data.as_slice()).unwrap();
+    assert_eq!(global_authority.super_admin, fixture.proposed_admin);
+    // The proposal is closed, so it can't be accepted twice
+    assert_eq!(accounts[2].lamports, 0);
+}
+
+#[test]
+fn legacy_whitelist_entries_can_be_removed() {
+    set_stubs();
+    let fixture = Fixture::new();
+    let (result, accounts) = fixture.execute("remove_legacy_whitelist", fixture.admin);
+    assert_eq!(result, Ok(()));
+    assert_eq!(accounts[5].lamports, 0);
+}
+
+#[test]
+fn farms_at_other_addresses_than_their_pda_are_rejected() {
+    set_stubs();
+    let fixture = Fixture {
+        farm_pool: Pubkey::new_unique(),
+        new_farm_pool: Pubkey::new_unique(),
+        ..Fixture::new()
+    };
+    let seeds_error = Err(ProgramError::Custom(ErrorCode::ConstraintSeeds as u32));
+    for instruction in [
+        "create_farm",
+        "update_farm",
+        "add_whitelist",
+        "remove_whitelist",
+        "set_stop",
+        "set_merkle_root",
+        "set_rarity_root",
+        "set_unstake_penalty",
+        "init_reward_vault",
+        "fund_reward_vault",
+    ] {
+        assert_eq!(fixture.run(instruction, fixture.admin), seeds_error, "{}", instruction);
+    }
+}
@@ -0,0 +1,187 @@
+//! Claiming the rewards of every staked NFT at once, across farms paying by minting and from vaults.
+
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{entrypoint::ProgramResult, program_pack::Pack, system_program};
+use anchor_lang::{AccountDeserialize, InstructionData};
+use common::harness::*;
+use common::rejection;
+use spl_token::state::Account as SplTokenAccount;
+use staking::account::{FarmData, GlobalPool, StakedData, UserPool};
+use staking::constants::*;
+use staking::error::StakingError;
+use staking::event::ClaimAllEvent;
+
+const USER_POOL: usize = 1;
+const USER_REWARD: usize = 3;
+// Vault of the third farm, after the first two farms' pairs
+const REWARD_VAULT: usize = 11;
+
+/// Farms 1 and 2 mint 1 and 2 per second, farm 3 pays 1 per second from its vault.
+/// The owner staked two NFTs in farm 1 100 seconds ago, one in farm 2 50 seconds ago
+/// and one in farm 3 10 seconds ago.
+struct ClaimFixture {
+    owner: Pubkey,
+    reward_mint: Pubkey,
+    farms: Vec<(Pubkey, FarmData)>,
+    stakes: Vec<StakedData>,
+}
+
+impl ClaimFixture {
+    fn new() -> Self {
+        let reward_mint = Pubkey::new_unique();
+        let farms: Vec<(Pubkey, FarmData)> = [(1u64, 1, false), (2, 2, false), (3, 1, true)].iter()
+            .map(|(farm_number, rate, vault)| {
+                let (key, bump) = Pubkey::find_program_address(
+                    &[FARM_SEED.as_ref(), farm_number.to_le_bytes().as_ref()],
+                    &staking::ID,
+                );
+                let mut farm = FarmData {
+                    farm_number: *farm_number,
+                    max_stake_count: 10,
+                    reward_mint,
+                    bump,
+                    ..FarmData::default()
+                };
+                if *vault {
+                    farm.reward_vault = pda(&[REWARD_VAULT_SEED.as_ref(), key.as_ref()]);
+                }
+                farm.set_tiers(&[0], &[*rate]).unwrap();
+                (key, farm)
+            })
+            .collect();
+        let stakes = [(0, 100), (0, 100), (1, 50), (2, 10)].iter()
+            .map(|(farm, age)| StakedData {
+                mint: Pubkey::new_unique(),
+                farm: farms[*farm].0,
+                farm_number: farms[*farm].1.farm_number,
+                staked_time: NOW - age,
+                claimed_time: NOW - age,
+                multiplier_bps: BPS_DENOMINATOR,
+                rarity_weight_bps: BPS_DENOMINATOR,
+                ..StakedData::default()
+            })
+            .collect();
+        ClaimFixture {
+            owner: Pubkey::new_unique(),
+            reward_mint,
+            farms,
+            stakes,
+        }
+    }
+
+    /// Claims with the `[farm_pool, reward_vault]` pairs of the farms at `farms`
+    fn claim_all(&self, farms: &[usize], farm_number: Option<u64>) -> (ProgramResult, Vec<TestAccount>) {
+        let global_authority = pda(&[GLOBAL_AUTHORITY_SEED.as_ref()]);
+        let (user_pool, bump) = Pubkey::find_program_address(&[USER_POOL_SEED.as_ref(), self.owner.as_ref()], &staking::ID);
+        let mut accounts = vec![
+            TestAccount::signer(self.owner),
+            program_account(user_pool, &UserPool {
+                owner: self.owner,
+                bump,
+                staking: self.stakes.clone(),
+            }),
+            program_account(global_authority, &GlobalPool::default()),
+            token_account(Pubkey::new_unique(), self.reward_mint, self.owner, 0),
+            mint_account(self.reward_mint, global_authority),
+            TestAccount::program(spl_token::ID),
+        ];
+        for (key, farm) in farms.iter().map(|index| &self.farms[*index]) {
+            accounts.push(farm_account(*key, farm));
+            accounts.push(if farm.reward_vault == Pubkey::default() {
+                TestAccount::new(Pubkey::new_unique(), system_program::ID, vec![])
+            } else {
+                token_account(farm.reward_vault, self.reward_mint, global_authority, 1_000)
+            });
+        }
+        execute(accounts, staking::instruction::ClaimAll {
+            global_bump: 0,
+            farm_number,
+        }.data())
+    }
+}
+
+fn token_amount(account: &TestAccount) -> u64 {
+    SplTokenAccount::unpack(&account.data).unwrap().amount
+}
+
+fn claimed_times(accounts: &[TestAccount]) -> Vec<i64> {
+    UserPool::try_deserialize(&mut accounts[USER_POOL].data.as_slice()).unwrap()
+        .staking.iter()
+        .map(|staked_data| staked_data.claimed_time)
+        .collect()
+}
+
+// Amounts of the token instructions with the `tag` that were invoked
+fn token_invocations(tag: u8) -> Vec<u64> {
+    invocations().iter()
+        .filter(|instruction| instruction.program_id == spl_token::ID && instruction.data[0] == tag)
+        .map(|instruction| u64::from_le_bytes(instruction.data[1..9].try_into().unwrap()))
+        .collect()
+}
+
+fn claimed_reward() -> u64 {
+    let events = events::<ClaimAllEvent>();
+    assert_eq!(events.len(), 1);
+    events[0].reward
+}
+
+#[test]
+fn minted_farms_are_paid_with_one_mint() {
+    let fixture = ClaimFixture::new();
+    let (result, accounts) = fixture.claim_all(&[0, 1, 2], None);
+    assert_eq!(result, Ok(()));
+
+    // 2 * 100 from farm 1 and 2 * 50 from farm 2 are minted, farm 3 transfers its 10
+    assert_eq!(token_invocations(7), vec![300]);
+    assert_eq!(token_invocations(3), vec![10]);
+    assert_eq!(token_amount(&accounts[USER_REWARD]), 310);
+    assert_eq!(token_amount(&accounts[REWARD_VAULT]), 990);
+    assert_eq!(claimed_reward(), 310);
+    assert_eq!(claimed_times(&accounts), vec![NOW; 4]);
+}
+
+#[test]
+fn farm_number_limits_the_claim() {
+    let fixture = ClaimFixture::new();
+    for farms in [&[0, 1, 2][..], &[1]] {
+        let (result, accounts) = fixture.claim_all(farms, Some(2));
+        assert_eq!(result, Ok(()));
+        assert_eq!(token_invocations(7), vec![100]);
+        assert_eq!(token_amount(&accounts[USER_REWARD]), 100);
+        assert_eq!(claimed_reward(), 100);
+        assert_eq!(claimed_times(&accounts), vec![NOW - 100, NOW - 100, NOW, NOW - 10]);
+    }
+}
+
+#[test]
+fn every_claimed_farm_has_to_be_passed() {
+    let fixture = ClaimFixture::new();
+    assert_eq!(fixture.claim_all(&[0, 1], None).0, rejection(StakingError::MissingFarmAccount));
+    assert_eq!(fixture.claim_all(&[0, 2], Some(2)).0, rejection(StakingError::MissingFarmAccount));
+}
+
+#[test]
+fn farms_paying_another_mint_are_rejected() {
+    let mut fixture = ClaimFixture::new();
+    fixture.farms[1].1.reward_mint = Pubkey::new_unique();
+    assert_eq!(fixture.claim_all(&[0, 1, 2], None).0, rejection(StakingError::InvalidRewardMint));
+
+    // Filtered out farms aren't checked
+    let (result, accounts) = fixture.claim_all(&[0, 1], Some(1));
+    assert_eq!(result, Ok(()));
+    assert_eq!(token_amount(&accounts[USER_REWARD]), 200);
+}
+
+#[test]
+fn stopped_farms_are_skipped() {
+    let mut fixture = ClaimFixture::new();
+    fixture.farms[1].1.is_stop = true;
+    let (result, accounts) = fixture.claim_all(&[0, 1, 2], None);
+    assert_eq!(result, Ok(()));
+    assert_eq!(token_invocations(7), vec![200]);
+    assert_eq!(token_amount(&accounts[USER_REWARD]), 210);
+    assert_eq!(claimed_reward(), 210);
+    assert_eq!(claimed_times(&accounts), vec![NOW, NOW, NOW - 50, NOW]);
+}
@@ -0,0 +1,484 @@
+//! Runs instructions through `staking::entry` with hand-built accounts,
+//! the syscall stubs stand in for the sysvars and the CPIs the instructions make.
+
+use std::cell::RefCell;
+use std::sync::Once;
+
+use anchor_lang::__private::bytemuck;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    entrypoint::ProgramResult,
+    instruction::Instruction,
+    program_pack::Pack,
+    program_stubs::{self, SyscallStubs},
+    system_program,
+};
+use anchor_lang::{AccountSerialize, AnchorDeserialize, AnchorSerialize, Discriminator, InstructionData};
+use mpl_token_metadata::state::{Collection, Creator, Metadata, MAX_METADATA_LEN};
+use spl_token::state::{Account as SplTokenAccount, AccountState, Mint as SplMint};
+use staking::account::{
+    FarmData, GlobalPool, RarityProof, ReceiptCounter, StakeReceipt, StakedData, UserPool, WhitelistKind, WhitelistProof,
+};
+use staking::constants::*;
+
+pub const NOW: i64 = 1_650_000_000;
+
+thread_local! {
+    // Data of the events logged by the instructions run on this thread
+    static EVENTS: RefCell<Vec<Vec<u8>>> = const { RefCell::new(vec![]) };
+    // Instructions the instructions run on this thread invoked
+    static INVOCATIONS: RefCell<Vec<Instruction>> = const { RefCell::new(vec![]) };
+}
+
+/// Events of type `T` emitted since the last `execute` on this thread
+pub fn events<T: Discriminator + AnchorDeserialize>() -> Vec<T> {
+    EVENTS.with(|events| events.borrow().iter()
+        .filter(|data| data[..8] == T::discriminator())
+        .map(|data| T::try_from_slice(&data[8..]).unwrap())
+        .collect())
+}
+
+/// Instructions invoked since the last `execute` on this thread
+pub fn invocations() -> Vec<Instruction> {
+    INVOCATIONS.with(|invocations| invocations.borrow().clone())
+}
+
+fn update_token_account(info: &AccountInfo, update: impl FnOnce(&mut SplTokenAccount) -> ProgramResult) -> ProgramResult {
+    let mut token_account = SplTokenAccount::unpack(&info.data.borrow())?;
+    update(&mut token_account)?;
+    token_account.pack_into_slice(&mut info.data.borrow_mut());
+    Ok(())
+}
+
+pub struct TestStubs;
+
+impl SyscallStubs for TestStubs {
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let clock = Clock {
+            unix_timestamp: NOW,
+            ..Clock::default()
+        };
+        unsafe { *(var_addr as *mut Clock) = clock };
+        0
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        0
+    }
+
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        EVENTS.with(|events| events.borrow_mut().push(fields[0].to_vec()));
+    }
+
+    // Only the effects the instructions rely on are simulated: system accounts get created,
+    // token accounts get initialized and token balances move, other CPIs do nothing
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        _signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        INVOCATIONS.with(|invocations| invocations.borrow_mut().push(instruction.clone()));
+        let find = |index: usize| {
+            account_infos.iter()
+                .find(|info| *info.key == instruction.accounts[index].pubkey)
+                .unwrap()
+        };
+        let data = &instruction.data;
+        if instruction.program_id == system_program::ID && data[..4] == [0, 0, 0, 0] {
+            let lamports = u64::from_le_bytes(data[4..12].try_into().unwrap());
+            let owner = Pubkey::new(&data[20..52]);
+            let (from, to) = (find(0), find(1));
+            **from.lamports.borrow_mut() -= lamports;
+            **to.lamports.borrow_mut() += lamports;
+            to.assign(&owner);
+        } else if instruction.program_id == spl_token::ID && data[0] == 1 {
+            let token_account = SplTokenAccount {
+                mint: instruction.accounts[1].pubkey,
+                owner: instruction.accounts[2].pubkey,
+                state: AccountState::Initialized,
+                ..SplTokenAccount::default()
+            };
+            token_account.pack_into_slice(&mut find(0).data.borrow_mut());
+        } else if instruction.program_id == spl_token::ID && (data[0] == 3 || data[0] == 7) {
+            let amount = u64::from_le_bytes(data[1..9].try_into().unwrap());
+            // Transfer takes from its source, MintTo adds to the mint's supply
+            if data[0] == 3 {
+                update_token_account(find(0), |source| {
+                    source.amount = source.amount.checked_sub(amount).ok_or(ProgramError::InsufficientFunds)?;
+                    Ok(())
+                })?;
+            } else {
+                let mint = find(0);
+                let mut supply = SplMint::unpack(&mint.data.borrow())?;
+                supply.supply += amount;
+                supply.pack_into_slice(&mut mint.data.borrow_mut());
+            }
+            update_token_account(find(1), |destination| {
+                destination.amount += amount;
+                Ok(())
+            })?;
+        }
+        Ok(())
+    }
+}
+
+pub fn set_stubs() {
+    static STUBS: Once = Once::new();
+    STUBS.call_once(|| {
+        program_stubs::set_syscall_stubs(Box::new(TestStubs));
+    });
+}
+
+pub struct TestAccount {
+    pub key: Pubkey,
+    pub owner: Pubkey,
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub is_signer: bool,
+    pub executable: bool,
+}
+
+impl TestAccount {
+    pub fn new(key: Pubkey, owner: Pubkey, data: Vec<u8>) -> Self {
+        TestAccount {
+            key,
+            owner,
+            lamports: 1_000_000_000,
+            data,
+            is_signer: false,
+            executable: false,
+        }
+    }
+
+    pub fn signer(key: Pubkey) -> Self {
+        TestAccount {
+            is_signer: true,
+            ..Self::new(key, system_program::ID, vec![])
+        }
+    }
+
+    pub fn program(key: Pubkey) -> Self {
+        TestAccount {
+            executable: true,
+            ..Self::new(key, Pubkey::default(), vec![])
+        }
+    }
+
+    // Not created yet, the instruction inits it with `space` bytes
+    pub fn uninitialized(key: Pubkey, space: usize) -> Self {
+        TestAccount {
+            lamports: 0,
+            ..Self::new(key, system_program::ID, vec![0; space])
+        }
+    }
+
+    pub fn info(&mut self) -> AccountInfo<'_> {
+        AccountInfo::new(
+            &self.key,
+            self.is_signer,
+            true,
+            &mut self.lamports,
+            &mut self.data,
+            &self.owner,
+            self.executable,
+            0,
+        )
+    }
+}
+
+pub fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &staking::ID).0
+}
+
+pub fn program_account<T: AccountSerialize>(key: Pubkey, account: &T) -> TestAccount {
+    let mut data = vec![];
+    account.try_serialize(&mut data).unwrap();
+    TestAccount::new(key, staking::ID, data)
+}
+
+pub fn farm_account(key: Pubkey, farm: &FarmData) -> TestAccount {
+    let mut data = FarmData::discriminator().to_vec();
+    data.extend_from_slice(bytemuck::bytes_of(farm));
+    TestAccount::new(key, staking::ID, data)
+}
+
+pub fn mint_account(key: Pubkey, authority: Pubkey) -> TestAccount {
+    let mint = SplMint {
+        mint_authority: Some(authority).into(),
+        decimals: 9,
+        is_initialized: true,
+        ..SplMint::default()
+    };
+    let mut data = vec![0; SplMint::LEN];
+    mint.pack_into_slice(&mut data);
+    TestAccount::new(key, spl_token::ID, data)
+}
+
+pub fn token_account(key: Pubkey, mint: Pubkey, owner: Pubkey, amount: u64) -> TestAccount {
+    let token_account = SplTokenAccount {
+        mint,
+        owner,
+        amount,
+        state: AccountState::Initialized,
+        ..SplTokenAccount::default()
+    };
+    let mut data = vec![0; SplTokenAccount::LEN];
+    token_account.pack_into_slice(&mut data);
+    TestAccount::new(key, spl_token::ID, data)
+}
+
+pub fn rent_sysvar() -> TestAccount {
+    let rent = Rent::default();
+    let mut data = rent.lamports_per_byte_year.to_le_bytes().to_vec();
+    data.extend_from_slice(&rent.exemption_threshold.to_le_bytes());
+    data.push(rent.burn_percent);
+    TestAccount::new(solana_program::sysvar::rent::ID, solana_program::sysvar::ID, data)
+}
+
+pub fn execute(mut accounts: Vec<TestAccount>, data: Vec<u8>) -> (ProgramResult, Vec<TestAccount>) {
+    set_stubs();
+    EVENTS.with(|events| events.borrow_mut().clear());
+    INVOCATIONS.with(|invocations| invocations.borrow_mut().clear());
+    let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+    let result = staking::entry(&staking::ID, &infos, &data);
+    drop(infos);
+    (result, accounts)
+}
+
+/// An owner staking an NFT into farm 1, which pays 1 per second and allows 10 stakes per owner.
+/// The farm, the NFT's metadata and the owner's earlier stakes can be changed before staking.
+pub struct StakeFixture {
+    pub owner: Pubkey,
+    pub nft_mint: Pubkey,
+    pub global_authority: Pubkey,
+    pub farm_pool: Pubkey,
+    pub farm: FarmData,
+    pub reward_mint: Pubkey,
+    pub metadata: Metadata,
+    /// Stakes already in the owner's user pool
+    pub pool_stakes: Vec<StakedData>,
+    /// Count of the owner's receipt counter, `None` while it isn't created
+    pub receipt_count: Option<u64>,
+}
+
+impl StakeFixture {
+    pub fn new() -> Self {
+        let (farm_pool, bump) = Pubkey::find_program_address(&[FARM_SEED.as_ref(), 1u64.to_le_bytes().as_ref()], &staking::ID);
+        let reward_mint = Pubkey::new_unique();
+        let mut farm = FarmData {
+            farm_number: 1,
+            max_stake_count: 10,
+            reward_mint,
+            bump,
+            ..FarmData::default()
+        };
+        farm.set_tiers(&[0], &[1]).unwrap();
+        StakeFixture {
+            owner: Pubkey::new_unique(),
+            nft_mint: Pubkey::new_unique(),
+            global_authority: pda(&[GLOBAL_AUTHORITY_SEED.as_ref()]),
+            farm_pool,
+            farm,
+            reward_mint,
+            metadata: Metadata::default(),
+            pool_stakes: vec![],
+            receipt_count: None,
+        }
+    }
+
+    pub fn with_creators(mut self, creators: &[(Pubkey, bool)]) -> Self {
+        self.metadata.data.creators = Some(creators.iter()
+            .map(|(address, verified)| Creator { address: *address, verified: *verified, share: 0 })
+            .collect());
+        self
+    }
+
+    pub fn with_update_authority(mut self, update_authority: Pubkey) -> Self {
+        self.metadata.update_authority = update_authority;
+        self
+    }
+
+    pub fn with_collection(mut self, key: Pubkey, verified: bool) -> Self {
+        self.metadata.collection = Some(Collection { key, verified });
+        self
+    }
+
+    /// Allow entry of `address` with a 2x multiplier
+    pub fn entry(&self, address: Pubkey, kind: WhitelistKind) -> TestAccount {
+        self.entry_at(pda(&[address.as_ref(), self.farm_pool.as_ref(), kind.seed()]), address, kind)
+    }
+
+    /// Allow entry stored at the address entries had before the kind seeds
+    pub fn legacy_entry(&self, address: Pubkey, kind: WhitelistKind) -> TestAccount {
+        self.entry_at(pda(&[address.as_ref(), self.farm_pool.as_ref()]), address, kind)
+    }
+
+    pub fn entry_at(&self, key: Pubkey, address: Pubkey, kind: WhitelistKind) -> TestAccount {
+        program_account(key, &WhitelistProof {
+            whitelist_address: address,
+            kind,
+            multiplier_bps: 20_000,
+        })
+    }
+
+    /// Deny entry of the staked mint, `created` or only its address
+    pub fn deny_entry(&self, created: bool) -> TestAccount {
+        let key = pda(&[self.nft_mint.as_ref(), self.farm_pool.as_ref(), WhitelistKind::Deny.seed()]);
+        if created {
+            self.entry_at(key, self.nft_mint, WhitelistKind::Deny)
+        } else {
+            TestAccount::new(key, system_program::ID, vec![])
+        }
+    }
+
+    pub fn receipt_counter(&self) -> Pubkey {
+        pda(&[RECEIPT_COUNTER_SEED.as_ref(), self.farm_pool.as_ref(), self.owner.as_ref()])
+    }
+
+    fn user_pool_account(&self) -> TestAccount {
+        let (key, bump) = Pubkey::find_program_address(&[USER_POOL_SEED.as_ref(), self.owner.as_ref()], &staking::ID);
+        let mut account = program_account(key, &UserPool {
+            owner: self.owner,
+            bump,
+            staking: self.pool_stakes.clone(),
+        });
+        // Room for the new stake, the pool doesn't need to grow
+        account.data.resize(UserPool::space(self.pool_stakes.len() + 1), 0);
+        account
+    }
+
+    /// While the counter isn't created it's left empty, or zeroed for the stakes that `init` it
+    fn receipt_counter_account(&self, init: bool) -> TestAccount {
+        let key = self.receipt_counter();
+        match self.receipt_count {
+            Some(count) => program_account(key, &ReceiptCounter {
+                bump: Pubkey::find_program_address(
+                    &[RECEIPT_COUNTER_SEED.as_ref(), self.farm_pool.as_ref(), self.owner.as_ref()],
+                    &staking::ID,
+                ).1,
+                count,
+            }),
+            None if init => TestAccount::uninitialized(key, ReceiptCounter::LEN),
+            None => TestAccount::new(key, system_program::ID, vec![]),
+        }
+    }
+
+    /// Accounts both stakes share after the owner's pools
+    fn stake_accounts(&self) -> Vec<TestAccount> {
+        let mint_metadata = Pubkey::find_program_address(
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                self.nft_mint.as_ref(),
+            ],
+            &mpl_token_metadata::id(),
+        ).0;
+        let mut metadata = self.metadata.try_to_vec().unwrap();
+        metadata.resize(MAX_METADATA_LEN, 0);
+        vec![
+            farm_account(self.farm_pool, &self.farm),
+            program_account(self.global_authority, &GlobalPool::default()),
+            token_account(Pubkey::new_unique(), self.nft_mint, self.owner, 1),
+            token_account(Pubkey::new_unique(), self.nft_mint, self.global_authority, 0),
+            TestAccount::new(self.nft_mint, system_program::ID, vec![]),
+            TestAccount::new(mint_metadata, mpl_token_metadata::id(), metadata),
+            TestAccount::program(spl_token::ID),
+            TestAccount::program(mpl_token_metadata::id()),
+            TestAccount::program(system_program::ID),
+        ]
+    }
+
+    pub fn stake_to_pool(
+        &self,
+        merkle_proof: Option<Vec<[u8; 32]>>,
+        rarity: Option<RarityProof>,
+        remaining: Vec<TestAccount>,
+    ) -> (ProgramResult, Vec<TestAccount>) {
+        let mut accounts = vec![
+            TestAccount::signer(self.owner),
+            self.user_pool_account(),
+            // Only read by the pool stake, it isn't created
+            self.receipt_counter_account(false),
+        ];
+        accounts.extend(self.stake_accounts());
+        accounts.extend(remaining);
+        execute(accounts, staking::instruction::StakeNftToPool {
+            _global_bump: 0,
+            merkle_proof,
+            rarity,
+        }.data())
+    }
+
+    pub fn stake_to_receipt(
+        &self,
+        merkle_proof: Option<Vec<[u8; 32]>>,
+        rarity: Option<RarityProof>,
+        remaining: Vec<TestAccount>,
+    ) -> (ProgramResult, Vec<TestAccount>) {
+        let mut accounts = vec![
+            TestAccount::signer(self.owner),
+            TestAccount::uninitialized(
+                pda(&[STAKE_RECEIPT_SEED.as_ref(), self.nft_mint.as_ref(), self.farm_pool.as_ref()]),
+                StakeReceipt::LEN,
+            ),
+            self.receipt_counter_account(true),
+            self.user_pool_account(),
+        ];
+        accounts.extend(self.stake_accounts());
+        accounts.extend(remaining);
+        execute(accounts, staking::instruction::StakeNftToReceipt {
+            _global_bump: 0,
+            merkle_proof,
+            rarity,
+        }.data())
+    }
+
+    /// Stake of the fixture's NFT in its farm since `staked_time`
+    pub fn staked_data(&self, staked_time: i64) -> StakedData {
+        StakedData {
+            mint: self.nft_mint,
+            farm: self.farm_pool,
+            farm_number: 1,
+            staked_time,
+            claimed_time: staked_time,
+            multiplier_bps: BPS_DENOMINATOR,
+            rarity_weight_bps: BPS_DENOMINATOR,
+            ..StakedData::default()
+        }
+    }
+
+    /// Unstakes the receipt of `stake`, the farm mints its reward
+    pub fn unstake_from_receipt(&self, stake: StakedData) -> (ProgramResult, Vec<TestAccount>) {
+        let (receipt, bump) = Pubkey::find_program_address(
+            &[STAKE_RECEIPT_SEED.as_ref(), self.nft_mint.as_ref(), self.farm_pool.as_ref()],
+            &staking::ID,
+        );
+        let mut farm = self.farm;
+        farm.staked_count += 1;
+        let accounts = vec![
+            TestAccount::signer(self.owner),
+            program_account(receipt, &StakeReceipt {
+                owner: self.owner,
+                bump,
+                stake,
+            }),
+            self.receipt_counter_account(false),
+            farm_account(self.farm_pool, &farm),
+            program_account(self.global_authority, &GlobalPool {
+                total_staked_count: 1,
+                ..GlobalPool::default()
+            }),
+            token_account(Pubkey::new_unique(), self.nft_mint, self.owner, 0),
+            token_account(Pubkey::new_unique(), self.nft_mint, self.global_authority, 1),
+            token_account(Pubkey::new_unique(), self.reward_mint, self.owner, 0),
+            mint_account(self.reward_mint, self.global_authority),
+            TestAccount::new(Pubkey::new_unique(), system_program::ID, vec![]),
+            TestAccount::new(Pubkey::new_unique(), system_program::ID, vec![]),
+            TestAccount::new(self.nft_mint, system_program::ID, vec![]),
+            TestAccount::program(spl_token::ID),
+        ];
+        execute(accounts, staking::instruction::UnstakeNftFromReceipt { global_bump: 0 }.data())
+    }
+}
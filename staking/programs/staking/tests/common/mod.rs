@@ -0,0 +1,31 @@
+//! Fixtures shared by the integration tests, each test binary only uses some of them
+#![allow(dead_code)]
+
+pub mod harness;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use staking::account::FarmData;
+use staking::error::StakingError;
+
+/// Farm paying `rate` per second from the start
+pub fn farm(farm_number: u64, rate: u64) -> FarmData {
+    let mut farm = FarmData {
+        farm_number,
+        ..Default::default()
+    };
+    farm.set_tiers(&[0], &[rate]).unwrap();
+    farm
+}
+
+/// Result of an instruction failing with `error`
+pub fn rejection(error: StakingError) -> ProgramResult {
+    Err(ProgramError::Custom(6000 + error as u32))
+}
+
+pub fn assert_error<T, E: Into<ProgramError>>(result: std::result::Result<T, E>, error: StakingError) {
+    match result {
+        Ok(_) => panic!("expected {:?}", error),
+        Err(err) => assert_eq!(Err(err.into()), rejection(error)),
+    }
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use common::{assert_error, farm};
+use staking::account::{FarmData, UserPool};
+use staking::constants::BPS_DENOMINATOR;
+use staking::error::StakingError;
+
+// An NFT staked at 0 in a farm paying 1 per second with a cooldown of 10 seconds
+fn staked_pool() -> (Pubkey, FarmData, Pubkey, UserPool) {
+    let (farm_key, mut farm) = (Pubkey::new_unique(), farm(0, 1));
+    farm.cooldown_duration = 10;
+    let mint = Pubkey::new_unique();
+    let mut user_pool = UserPool::default();
+    user_pool.add_nft(mint, farm_key, farm.farm_number, BPS_DENOMINATOR, BPS_DENOMINATOR, 0).unwrap();
+    (farm_key, farm, mint, user_pool)
+}
+
+#[test]
+fn unstake_is_requested_once() {
+    let (farm_key, farm, mint, mut user_pool) = staked_pool();
+    user_pool.request_unstake(mint, farm_key, &farm, 50).unwrap();
+    assert_eq!(user_pool.staking[0].unstake_requested_time, 50);
+    assert_error(user_pool.request_unstake(mint, farm_key, &farm, 55), StakingError::UnstakeAlreadyRequested);
+    assert_eq!(user_pool.staking[0].unstake_requested_time, 50);
+}
+
+#[test]
+fn locked_nfts_cant_request_an_unstake() {
+    let (farm_key, mut farm, mint, mut user_pool) = staked_pool();
+    farm.min_lock_duration = 50;
+    assert_error(user_pool.request_unstake(mint, farm_key, &farm, 49), StakingError::InvalidWithdrawTime);
+    user_pool.request_unstake(mint, farm_key, &farm, 50).unwrap();
+}
+
+#[test]
+fn cooldown_ends_at_its_boundary() {
+    let (farm_key, farm, mint, mut user_pool) = staked_pool();
+    assert_error(farm.check_cooldown(&user_pool.staking[0], 100), StakingError::UnstakeNotRequested);
+
+    user_pool.request_unstake(mint, farm_key, &farm, 50).unwrap();
+    assert_error(farm.check_cooldown(&user_pool.staking[0], 59), StakingError::InvalidWithdrawTime);
+    farm.check_cooldown(&user_pool.staking[0], 60).unwrap();
+}
+
+#[test]
+fn accrual_stops_at_the_request() {
+    let (farm_key, farm, mint, mut user_pool) = staked_pool();
+    assert_eq!(user_pool.claim_reward(mint, farm_key, &farm, 20).unwrap(), 20);
+    user_pool.request_unstake(mint, farm_key, &farm, 50).unwrap();
+
+    // Claims after the request only get the reward up to it
+    assert_eq!(user_pool.claim_reward(mint, farm_key, &farm, 80).unwrap(), 30);
+    assert_eq!(user_pool.claim_reward(mint, farm_key, &farm, 90).unwrap(), 0);
+    let (_, reward) = user_pool.remove_nft(mint, farm_key, &farm, 100).unwrap();
+    assert_eq!(reward, 0);
+}
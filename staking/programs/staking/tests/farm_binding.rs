@@ -0,0 +1,49 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use common::{assert_error, farm};
+use staking::account::UserPool;
+use staking::error::StakingError;
+
+#[test]
+fn stake_pays_under_its_own_farm() {
+    let (low_key, low_farm) = (Pubkey::new_unique(), farm(0, 1));
+    let mint = Pubkey::new_unique();
+    let mut user_pool = UserPool::default();
+    user_pool.add_nft(mint, low_key, low_farm.farm_number, 10_000, 10_000, 100).unwrap();
+
+    assert_eq!(user_pool.claim_reward(mint, low_key, &low_farm, 110).unwrap(), 10);
+    let (staked_data, reward) = user_pool.remove_nft(mint, low_key, &low_farm, 120).unwrap();
+    assert_eq!(staked_data.farm, low_key);
+    assert_eq!(reward, 10);
+}
+
+#[test]
+fn stake_is_rejected_by_another_farm() {
+    let (low_key, low_farm) = (Pubkey::new_unique(), farm(0, 1));
+    let (high_key, high_farm) = (Pubkey::new_unique(), farm(1, 1_000));
+    let mint = Pubkey::new_unique();
+    let mut user_pool = UserPool::default();
+    user_pool.add_nft(mint, low_key, low_farm.farm_number, 10_000, 10_000, 100).unwrap();
+
+    assert_error(user_pool.claim_reward(mint, high_key, &high_farm, 110), StakingError::InvalidFarmPool);
+    assert_eq!(user_pool.claim_farm_reward(high_key, &high_farm, 110).unwrap(), 0);
+    assert_error(user_pool.request_unstake(mint, high_key, &high_farm, 110), StakingError::InvalidFarmPool);
+    assert_error(user_pool.remove_nft(mint, high_key, &high_farm, 110), StakingError::InvalidFarmPool);
+    assert_error(user_pool.take_nft(mint, high_key), StakingError::InvalidFarmPool);
+
+    // Nothing was claimed or removed by the rejected calls
+    assert_eq!(user_pool.staking.len(), 1);
+    assert_eq!(user_pool.claim_reward(mint, low_key, &low_farm, 110).unwrap(), 10);
+}
+
+#[test]
+fn same_farm_number_doesnt_match_another_farm() {
+    let (farm_key, farm_pool) = (Pubkey::new_unique(), farm(3, 1));
+    let other_key = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let mut user_pool = UserPool::default();
+    user_pool.add_nft(mint, farm_key, farm_pool.farm_number, 10_000, 10_000, 100).unwrap();
+
+    assert_error(user_pool.claim_reward(mint, other_key, &farm_pool, 110), StakingError::InvalidFarmPool);
+}
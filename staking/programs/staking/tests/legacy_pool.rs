@@ -0,0 +1,95 @@
+//! NFTs staked in the user pools of the first program version can still be unstaked.
+
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::{Discriminator, InstructionData};
+use common::harness::*;
+use common::rejection;
+use staking::account::{GlobalPool, LegacyStakedData, LegacyUserPool, UserPool};
+use staking::constants::*;
+use staking::error::StakingError;
+
+fn legacy_user_pool(key: Pubkey, owner: Pubkey, mints: &[Pubkey]) -> TestAccount {
+    let mut account = TestAccount::new(key, staking::ID, vec![0; LegacyUserPool::LEN]);
+    account.data[..8].copy_from_slice(&UserPool::discriminator());
+    account.data[8..40].copy_from_slice(owner.as_ref());
+    let info = account.info();
+    LegacyUserPool {
+        owner,
+        staking: mints.iter()
+            .map(|mint| LegacyStakedData {
+                mint: *mint,
+                farm_number: 1,
+                staked_time: NOW - 100,
+                claimed_time: NOW - 100,
+            })
+            .collect(),
+    }.store(&info).unwrap();
+    drop(info);
+    account
+}
+
+fn unstake(owner: Pubkey, user_pool: TestAccount, nft_mint: Pubkey) -> (ProgramResult, Vec<TestAccount>) {
+    let global_authority = pda(&[GLOBAL_AUTHORITY_SEED.as_ref()]);
+    let accounts = vec![
+        TestAccount::signer(owner),
+        user_pool,
+        program_account(global_authority, &GlobalPool {
+            total_staked_count: 2,
+            ..GlobalPool::default()
+        }),
+        token_account(Pubkey::new_unique(), nft_mint, owner, 0),
+        token_account(Pubkey::new_unique(), nft_mint, global_authority, 1),
+        TestAccount::new(nft_mint, Pubkey::default(), vec![]),
+        TestAccount::program(spl_token::ID),
+    ];
+    execute(accounts, staking::instruction::UnstakeLegacyNft { global_bump: 0 }.data())
+}
+
+#[test]
+fn legacy_nfts_are_returned_and_the_pool_closed_with_the_last() {
+    let owner = Pubkey::new_unique();
+    let key = LegacyUserPool::address(&owner).unwrap();
+    let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    let (result, mut accounts) = unstake(owner, legacy_user_pool(key, owner, &[first, second]), first);
+    assert_eq!(result, Ok(()));
+    let info = accounts[1].info();
+    let user_pool = LegacyUserPool::load(&info).unwrap();
+    drop(info);
+    assert_eq!(user_pool.staking.len(), 1);
+    assert_eq!(user_pool.staking[0].mint, second);
+    assert_eq!(user_pool.staking[0].staked_time, NOW - 100);
+    assert_ne!(accounts[1].lamports, 0);
+
+    let (result, accounts) = unstake(owner, accounts.swap_remove(1), second);
+    assert_eq!(result, Ok(()));
+    assert_eq!(accounts[1].lamports, 0);
+    assert_eq!(accounts[0].lamports, 2_000_000_000);
+}
+
+#[test]
+fn only_the_owners_legacy_pool_is_accepted() {
+    let owner = Pubkey::new_unique();
+    let nft_mint = Pubkey::new_unique();
+
+    // Another owner's pool, at its own address or at the signer's
+    let other = Pubkey::new_unique();
+    for key in [LegacyUserPool::address(&other).unwrap(), LegacyUserPool::address(&owner).unwrap()] {
+        let user_pool = legacy_user_pool(key, other, &[nft_mint]);
+        assert_eq!(unstake(owner, user_pool, nft_mint).0, rejection(StakingError::InvalidUserPool));
+    }
+
+    // A current user pool isn't read as a legacy one
+    let mut user_pool = program_account(LegacyUserPool::address(&owner).unwrap(), &UserPool {
+        owner,
+        ..UserPool::default()
+    });
+    user_pool.data.resize(UserPool::space(1), 0);
+    assert_eq!(unstake(owner, user_pool, nft_mint).0, rejection(StakingError::InvalidUserPool));
+
+    let user_pool = legacy_user_pool(LegacyUserPool::address(&owner).unwrap(), owner, &[Pubkey::new_unique()]);
+    assert_eq!(unstake(owner, user_pool, nft_mint).0, rejection(StakingError::InvalidNFTAddress));
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use common::{assert_error, farm};
+use staking::constants::MAX_RATE_HISTORY;
+use staking::error::StakingError;
+
+#[test]
+fn unchanged_schedule_takes_no_checkpoint() {
+    let mut farm = farm(0, 1);
+    farm.staked_count = 1;
+    for update in 1..=2 * MAX_RATE_HISTORY {
+        farm.update_tiers(&[0], &[1], update as i64 * 10).unwrap();
+    }
+    assert_eq!(farm.rate_history_count, 0);
+    assert_eq!(farm.rate_version, 0);
+
+    farm.update_tiers(&[0], &[2], 100).unwrap();
+    assert_eq!(farm.rate_history_count, 1);
+    assert_eq!(farm.calculate_reward(0, 0, 110).unwrap(), 120);
+}
+
+#[test]
+fn empty_farm_drops_its_history() {
+    let mut farm = farm(0, 1);
+    farm.staked_count = 1;
+    for update in 1..MAX_RATE_HISTORY {
+        farm.update_tiers(&[0], &[update as u64 + 1], update as i64 * 10).unwrap();
+    }
+
+    farm.staked_count = 0;
+    farm.update_tiers(&[0], &[100], 100).unwrap();
+    assert_eq!(farm.rate_history_count, 0);
+    assert_eq!(farm.rate_version, MAX_RATE_HISTORY as u64);
+
+    // NFTs staked from now on accrue under the new schedule only
+    farm.staked_count = 1;
+    assert_eq!(farm.calculate_reward(100, 100, 110).unwrap(), 1_000);
+    for update in 1..=MAX_RATE_HISTORY {
+        farm.update_tiers(&[0], &[100 + update as u64], 100 + update as i64 * 10).unwrap();
+    }
+}
+
+#[test]
+fn full_history_of_a_staked_farm_rejects_a_rate_change() {
+    // Rate 1 from the start, then one more every 10 seconds
+    let mut farm = farm(0, 1);
+    farm.staked_count = 1;
+    for update in 1..=MAX_RATE_HISTORY {
+        farm.update_tiers(&[0], &[update as u64 + 1], update as i64 * 10).unwrap();
+    }
+    assert_error(farm.update_tiers(&[0], &[100], 90), StakingError::RateHistoryFull);
+    // Other farm settings can still be updated with the same schedule
+    farm.update_tiers(&[0], &[MAX_RATE_HISTORY as u64 + 1], 90).unwrap();
+
+    // 10 seconds at each rate from 1 to 8, then 20 seconds at 9
+    assert_eq!(farm.calculate_reward(0, 0, 100).unwrap(), 540);
+}
@@ -0,0 +1,72 @@
+//! Receipt stakes and user pool stakes of an owner together count against the farm's `max_stake_count`.
+
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::AccountDeserialize;
+use common::harness::*;
+use common::rejection;
+use staking::account::{ReceiptCounter, StakedData};
+use staking::error::StakingError;
+
+/// An owner with one NFT in the user pool of a farm allowing two stakes per owner,
+/// staking a mint allowed by the farm's merkle root
+fn fixture(receipt_count: Option<u64>) -> StakeFixture {
+    let mut fixture = StakeFixture::new();
+    fixture.farm.max_stake_count = 2;
+    fixture.farm.merkle_root = staking::merkle::leaf_hash(&fixture.nft_mint);
+    fixture.pool_stakes = vec![StakedData {
+        mint: Pubkey::new_unique(),
+        farm: fixture.farm_pool,
+        farm_number: 1,
+        ..StakedData::default()
+    }];
+    fixture.receipt_count = receipt_count;
+    fixture
+}
+
+fn stake_to_receipt(fixture: &StakeFixture) -> (ProgramResult, Vec<TestAccount>) {
+    fixture.stake_to_receipt(Some(vec![]), None, vec![fixture.deny_entry(false)])
+}
+
+fn stake_to_pool(fixture: &StakeFixture) -> (ProgramResult, Vec<TestAccount>) {
+    fixture.stake_to_pool(Some(vec![]), None, vec![fixture.deny_entry(false)])
+}
+
+#[test]
+fn receipts_count_against_the_max_stake_count() {
+    let fixture = fixture(Some(1));
+    assert_eq!(stake_to_receipt(&fixture).0, rejection(StakingError::ExceedMaxCount));
+    assert_eq!(stake_to_pool(&fixture).0, rejection(StakingError::ExceedMaxCount));
+}
+
+#[test]
+fn first_receipt_creates_the_counter() {
+    let (result, accounts) = stake_to_receipt(&fixture(None));
+    assert_eq!(result, Ok(()));
+    let receipt_counter = ReceiptCounter::try_deserialize(&mut accounts[2].data.as_slice()).unwrap();
+    assert_eq!(receipt_counter.count, 1);
+}
+
+#[test]
+fn pool_stakes_without_receipts_pass() {
+    assert_eq!(stake_to_pool(&fixture(None)).0, Ok(()));
+}
+
+#[test]
+fn last_receipt_unstake_closes_the_counter() {
+    let fixture = fixture(Some(2));
+    let (result, accounts) = fixture.unstake_from_receipt(fixture.staked_data(NOW - 100));
+    assert_eq!(result, Ok(()));
+    let receipt_counter = ReceiptCounter::try_deserialize(&mut accounts[2].data.as_slice()).unwrap();
+    assert_eq!(receipt_counter.count, 1);
+    assert_ne!(accounts[2].lamports, 0);
+
+    let fixture = StakeFixture { receipt_count: Some(1), ..fixture };
+    let (result, accounts) = fixture.unstake_from_receipt(fixture.staked_data(NOW - 100));
+    assert_eq!(result, Ok(()));
+    // The owner gets the counter's rent back with the receipt's
+    assert_eq!(accounts[2].lamports, 0);
+    assert_eq!(accounts[0].lamports, 3_000_000_000);
+}
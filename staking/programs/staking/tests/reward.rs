@@ -0,0 +1,82 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::{assert_error, farm};
+use staking::account::{StakedData, UserPool};
+use staking::constants::*;
+use staking::error::StakingError;
+use staking::reward::{accrued_reward, apply_multiplier, calculate_reward};
+
+// 10 seconds at 1 per second, then 5 per second
+const TIER_DURATION: [i64; 2] = [10, 0];
+const TIER_RATE: [u64; 2] = [1, 5];
+
+#[test]
+fn tiers_switch_at_their_boundary() {
+    assert_eq!(accrued_reward(&TIER_DURATION, &TIER_RATE, 9).unwrap(), 9);
+    assert_eq!(accrued_reward(&TIER_DURATION, &TIER_RATE, 10).unwrap(), 10);
+    assert_eq!(accrued_reward(&TIER_DURATION, &TIER_RATE, 11).unwrap(), 15);
+    assert_eq!(accrued_reward(&TIER_DURATION, &TIER_RATE, 0).unwrap(), 0);
+}
+
+#[test]
+fn claims_dont_reset_the_tier() {
+    // 10 in the first tier and 25 in the second, of which 5 were claimed
+    assert_eq!(calculate_reward(&TIER_DURATION, &TIER_RATE, 100, 105, 115).unwrap(), 30);
+    assert_eq!(calculate_reward(&TIER_DURATION, &TIER_RATE, 100, 115, 116).unwrap(), 5);
+}
+
+#[test]
+fn lagging_clock_accrues_nothing() {
+    assert_eq!(calculate_reward(&TIER_DURATION, &TIER_RATE, 100, 110, 105).unwrap(), 0);
+    assert_eq!(calculate_reward(&TIER_DURATION, &TIER_RATE, 100, 100, 90).unwrap(), 0);
+    assert_eq!(accrued_reward(&TIER_DURATION, &TIER_RATE, -5).unwrap(), 0);
+}
+
+#[test]
+fn overflow_is_an_error() {
+    assert_error(accrued_reward(&[0], &[u64::MAX], 2), StakingError::RewardOverflow);
+    assert_error(accrued_reward(&[10, 0], &[u64::MAX, 1], 10), StakingError::RewardOverflow);
+    assert_error(calculate_reward(&[0], &[u64::MAX], 0, 0, i64::MAX), StakingError::RewardOverflow);
+}
+
+#[test]
+fn malformed_schedule_is_rejected() {
+    assert_error(accrued_reward(&[], &[], 10), StakingError::InvalidInput);
+    assert_error(accrued_reward(&[10], &[1, 5], 10), StakingError::InvalidInput);
+}
+
+#[test]
+fn max_rate_fits_the_horizon() {
+    let reward = accrued_reward(&[0], &[MAX_TIER_RATE], MAX_REWARD_HORIZON).unwrap();
+    let reward = apply_multiplier(reward, MAX_REWARD_MULTIPLIER_BPS).unwrap();
+    assert!(apply_multiplier(reward, MAX_RARITY_WEIGHT_BPS).is_ok());
+}
+
+#[test]
+fn overflowing_reward_saturates_on_unstake() {
+    let mut farm = farm(0, 1);
+    farm.tier_rate[0] = u64::MAX;
+    let (farm_pubkey, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut pool = UserPool::default();
+    pool.add_nft(mint, farm_pubkey, 0, BPS_DENOMINATOR, BPS_DENOMINATOR, 0).unwrap();
+
+    let (staked_data, reward) = pool.remove_nft(mint, farm_pubkey, &farm, 10).unwrap();
+    assert_eq!(staked_data.mint, mint);
+    assert_eq!(reward, u64::MAX);
+}
+
+#[test]
+fn unstake_reward_stops_at_the_horizon() {
+    let mut farm = farm(0, MAX_TIER_RATE);
+    farm.staked_count = 1;
+    let staked_data = StakedData {
+        claimed_time: 100,
+        multiplier_bps: MAX_REWARD_MULTIPLIER_BPS,
+        rarity_weight_bps: MAX_RARITY_WEIGHT_BPS,
+        ..StakedData::default()
+    };
+    let at_horizon = staked_data.unstake_reward(&farm, 100 + MAX_REWARD_HORIZON);
+    assert_eq!(at_horizon, MAX_TIER_RATE * MAX_REWARD_HORIZON as u64 * 100);
+    assert_eq!(staked_data.unstake_reward(&farm, i64::MAX), at_horizon);
+}
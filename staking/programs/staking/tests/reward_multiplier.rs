@@ -0,0 +1,57 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use common::{assert_error, farm};
+use staking::account::{StakedData, UserPool};
+use staking::error::StakingError;
+use staking::reward::apply_multiplier;
+
+#[test]
+fn multiplier_scales_claims_and_unstakes() {
+    let (farm_key, farm_pool) = (Pubkey::new_unique(), farm(0, 10));
+    let (single, double) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut user_pool = UserPool::default();
+    user_pool.add_nft(single, farm_key, 0, 10_000, 10_000, 100).unwrap();
+    user_pool.add_nft(double, farm_key, 0, 20_000, 10_000, 100).unwrap();
+
+    assert_eq!(user_pool.claim_reward(single, farm_key, &farm_pool, 110).unwrap(), 100);
+    assert_eq!(user_pool.claim_reward(double, farm_key, &farm_pool, 110).unwrap(), 200);
+    assert_eq!(user_pool.claim_farm_reward(farm_key, &farm_pool, 120).unwrap(), 300);
+
+    let (staked_data, reward) = user_pool.remove_nft(double, farm_key, &farm_pool, 125).unwrap();
+    assert_eq!(staked_data.multiplier_bps, 20_000);
+    assert_eq!(reward, 100);
+}
+
+#[test]
+fn multiplier_below_the_rate_rounds_down() {
+    let farm_pool = farm(0, 3);
+    let staked_data = StakedData {
+        staked_time: 100,
+        claimed_time: 100,
+        multiplier_bps: 5_000,
+        rarity_weight_bps: 10_000,
+        ..Default::default()
+    };
+
+    assert_eq!(staked_data.pending_reward(&farm_pool, 101).unwrap(), 1);
+}
+
+#[test]
+fn rarity_weight_stacks_with_the_multiplier() {
+    let farm_pool = farm(0, 10);
+    let staked_data = StakedData {
+        staked_time: 100,
+        claimed_time: 100,
+        multiplier_bps: 20_000,
+        rarity_weight_bps: 15_000,
+        ..Default::default()
+    };
+
+    assert_eq!(staked_data.pending_reward(&farm_pool, 110).unwrap(), 300);
+}
+
+#[test]
+fn multiplied_reward_overflow_is_rejected() {
+    assert_error(apply_multiplier(u64::MAX, 20_000), StakingError::RewardOverflow);
+}
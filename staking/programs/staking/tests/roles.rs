@@ -0,0 +1,40 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use common::assert_error;
+use staking::account::{AdminRoles, Role};
+use staking::constants::MAX_ROLE_GRANTS;
+use staking::error::StakingError;
+
+#[test]
+fn roles_are_granted_and_revoked_per_operator() {
+    let (super_admin, operator) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut admin_roles = AdminRoles::default();
+    assert!(admin_roles.allows(&super_admin, &super_admin, Role::TreasuryManager));
+    assert!(!admin_roles.allows(&super_admin, &operator, Role::FarmManager));
+
+    admin_roles.grant(operator, Role::FarmManager).unwrap();
+    admin_roles.grant(operator, Role::WhitelistManager).unwrap();
+    assert!(admin_roles.allows(&super_admin, &operator, Role::FarmManager));
+    assert!(!admin_roles.allows(&super_admin, &operator, Role::TreasuryManager));
+    assert_error(admin_roles.grant(operator, Role::FarmManager), StakingError::RoleAlreadyGranted);
+
+    admin_roles.revoke(operator, Role::FarmManager).unwrap();
+    assert!(!admin_roles.allows(&super_admin, &operator, Role::FarmManager));
+    assert!(admin_roles.allows(&super_admin, &operator, Role::WhitelistManager));
+    assert_error(admin_roles.revoke(operator, Role::FarmManager), StakingError::RoleNotGranted);
+}
+
+#[test]
+fn grants_are_capped() {
+    let mut admin_roles = AdminRoles::default();
+    for _ in 0..MAX_ROLE_GRANTS {
+        admin_roles.grant(Pubkey::new_unique(), Role::FarmManager).unwrap();
+    }
+    assert_error(admin_roles.grant(Pubkey::new_unique(), Role::FarmManager), StakingError::TooManyRoleGrants);
+
+    // A full account still serializes within its space
+    let mut data = vec![];
+    admin_roles.try_serialize(&mut data).unwrap();
+    assert_eq!(data.len(), AdminRoles::LEN);
+}
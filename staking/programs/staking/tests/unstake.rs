@@ -0,0 +1,230 @@
+//! Unstaking returns the NFT once its lock and the farm's cooldown have passed, even when the farm's reward vault can't pay the whole reward.
+
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{entrypoint::ProgramResult, program_pack::Pack, system_program};
+use anchor_lang::{AccountDeserialize, InstructionData};
+use common::harness::*;
+use common::{assert_error, rejection};
+use spl_token::state::Account as SplTokenAccount;
+use staking::account::{FarmData, GlobalPool, StakedData, UserPool};
+use staking::constants::*;
+use staking::error::StakingError;
+use staking::event::UnstakeEvent;
+
+const USER_REWARD: usize = 6;
+const REWARD_VAULT: usize = 8;
+const PENALTY_TREASURY: usize = 9;
+
+/// An NFT staked 100 seconds ago in a farm paying 1 per second from a vault holding `vault_amount`,
+/// `configure` changes the farm before the unstake
+fn unstake(vault_amount: u64, configure: impl FnOnce(&mut FarmData)) -> (ProgramResult, Vec<TestAccount>) {
+    execute_unstake(
+        staking::instruction::UnstakeNftFromPool { global_bump: 0 }.data(),
+        vault_amount,
+        |farm, _| configure(farm),
+    )
+}
+
+/// Finalizes the unstake of the NFT requested at `requested_time` in a farm with a `cooldown_duration`
+fn finalize_unstake(requested_time: i64, cooldown_duration: i64) -> (ProgramResult, Vec<TestAccount>) {
+    execute_unstake(
+        staking::instruction::FinalizeUnstake { global_bump: 0 }.data(),
+        1_000,
+        |farm, staked_data| {
+            farm.cooldown_duration = cooldown_duration;
+            staked_data.unstake_requested_time = requested_time;
+        },
+    )
+}
+
+fn execute_unstake(
+    data: Vec<u8>,
+    vault_amount: u64,
+    configure: impl FnOnce(&mut FarmData, &mut StakedData),
+) -> (ProgramResult, Vec<TestAccount>) {
+    let owner = Pubkey::new_unique();
+    let nft_mint = Pubkey::new_unique();
+    let reward_mint = Pubkey::new_unique();
+    let global_authority = pda(&[GLOBAL_AUTHORITY_SEED.as_ref()]);
+    let (farm_pool, farm_bump) = Pubkey::find_program_address(&[FARM_SEED.as_ref(), 1u64.to_le_bytes().as_ref()], &staking::ID);
+    let reward_vault = pda(&[REWARD_VAULT_SEED.as_ref(), farm_pool.as_ref()]);
+    let (user_pool, user_bump) = Pubkey::find_program_address(&[USER_POOL_SEED.as_ref(), owner.as_ref()], &staking::ID);
+
+    let mut farm = FarmData {
+        farm_number: 1,
+        max_stake_count: 10,
+        staked_count: 1,
+        reward_mint,
+        reward_vault,
+        bump: farm_bump,
+        ..FarmData::default()
+    };
+    farm.set_tiers(&[0], &[1]).unwrap();
+    let mut staked_data = StakedData {
+        mint: nft_mint,
+        farm: farm_pool,
+        farm_number: 1,
+        staked_time: NOW - 100,
+        claimed_time: NOW - 100,
+        multiplier_bps: BPS_DENOMINATOR,
+        rarity_weight_bps: BPS_DENOMINATOR,
+        ..StakedData::default()
+    };
+    configure(&mut farm, &mut staked_data);
+
+    let accounts = vec![
+        TestAccount::signer(owner),
+        program_account(user_pool, &UserPool {
+            owner,
+            bump: user_bump,
+            staking: vec![staked_data],
+        }),
+        farm_account(farm_pool, &farm),
+        program_account(global_authority, &GlobalPool {
+            super_admin: Pubkey::new_unique(),
+            reward_token: reward_mint,
+            total_staked_count: 1,
+            farm_count: 1,
+        }),
+        token_account(Pubkey::new_unique(), nft_mint, owner, 0),
+        token_account(Pubkey::new_unique(), nft_mint, global_authority, 1),
+        token_account(Pubkey::new_unique(), reward_mint, owner, 0),
+        mint_account(reward_mint, global_authority),
+        token_account(reward_vault, reward_mint, global_authority, vault_amount),
+        token_account(farm.penalty_treasury, reward_mint, Pubkey::new_unique(), 0),
+        TestAccount::new(nft_mint, system_program::ID, vec![]),
+        TestAccount::program(spl_token::ID),
+    ];
+    execute(accounts, data)
+}
+
+fn staked_count(accounts: &[TestAccount]) -> usize {
+    UserPool::try_deserialize(&mut accounts[1].data.as_slice()).unwrap().staking.len()
+}
+
+fn token_amount(account: &TestAccount) -> u64 {
+    SplTokenAccount::unpack(&account.data).unwrap().amount
+}
+
+// Reward, penalty and unpaid reward of the unstake's event
+fn unstake_event() -> (u64, u64, u64) {
+    let events = events::<UnstakeEvent>();
+    assert_eq!(events.len(), 1);
+    (events[0].reward, events[0].penalty, events[0].unpaid_reward)
+}
+
+#[test]
+fn unstakes_against_an_empty_vault() {
+    let (result, accounts) = unstake(0, |_| {});
+    assert_eq!(result, Ok(()));
+    assert_eq!(staked_count(&accounts), 0);
+    assert_eq!(token_amount(&accounts[USER_REWARD]), 0);
+    assert_eq!(unstake_event(), (100, 0, 100));
+}
+
+#[test]
+fn short_vault_pays_what_it_holds() {
+    let (result, accounts) = unstake(99, |_| {});
+    assert_eq!(result, Ok(()));
+    assert_eq!(staked_count(&accounts), 0);
+    assert_eq!(token_amount(&accounts[USER_REWARD]), 99);
+    assert_eq!(token_amount(&accounts[REWARD_VAULT]), 0);
+    assert_eq!(unstake_event(), (100, 0, 1));
+}
+
+#[test]
+fn unstakes_against_a_funded_vault() {
+    let (result, accounts) = unstake(1_000, |_| {});
+    assert_eq!(result, Ok(()));
+    assert_eq!(staked_count(&accounts), 0);
+    assert_eq!(token_amount(&accounts[USER_REWARD]), 100);
+    assert_eq!(token_amount(&accounts[REWARD_VAULT]), 900);
+    assert_eq!(unstake_event(), (100, 0, 0));
+}
+
+#[test]
+fn owner_is_paid_before_the_penalty_treasury() {
+    let with_treasury = |farm: &mut FarmData| {
+        farm.penalty_duration = 1_000;
+        farm.penalty_bps = 5_000;
+        farm.penalty_treasury = Pubkey::new_unique();
+    };
+    let (result, accounts) = unstake(70, with_treasury);
+    assert_eq!(result, Ok(()));
+    assert_eq!(token_amount(&accounts[USER_REWARD]), 50);
+    assert_eq!(token_amount(&accounts[PENALTY_TREASURY]), 20);
+    assert_eq!(unstake_event(), (100, 50, 0));
+
+    let (result, accounts) = unstake(30, with_treasury);
+    assert_eq!(result, Ok(()));
+    assert_eq!(token_amount(&accounts[USER_REWARD]), 30);
+    assert_eq!(token_amount(&accounts[PENALTY_TREASURY]), 0);
+    assert_eq!(unstake_event(), (100, 50, 20));
+}
+
+#[test]
+fn stopped_farm_reports_the_whole_reward_as_unpaid() {
+    let (result, accounts) = unstake(1_000, |farm| farm.is_stop = true);
+    assert_eq!(result, Ok(()));
+    assert_eq!(staked_count(&accounts), 0);
+    assert_eq!(token_amount(&accounts[USER_REWARD]), 0);
+    assert_eq!(token_amount(&accounts[REWARD_VAULT]), 1_000);
+    assert_eq!(unstake_event(), (100, 0, 100));
+}
+
+#[test]
+fn locked_nfts_cant_be_unstaked() {
+    let farm = FarmData {
+        min_lock_duration: 100,
+        ..FarmData::default()
+    };
+    assert_error(farm.check_unlocked(NOW - 99, NOW), StakingError::InvalidWithdrawTime);
+    farm.check_unlocked(NOW - 100, NOW).unwrap();
+    // A lock running past the end of time doesn't overflow
+    let farm = FarmData {
+        min_lock_duration: i64::MAX,
+        ..FarmData::default()
+    };
+    assert_error(farm.check_unlocked(NOW, i64::MAX - 1), StakingError::InvalidWithdrawTime);
+
+    // The NFT was staked 100 seconds ago
+    let (result, _) = unstake(1_000, |farm| farm.min_lock_duration = 101);
+    assert_eq!(result, rejection(StakingError::InvalidWithdrawTime));
+    let (result, accounts) = unstake(1_000, |farm| farm.min_lock_duration = 100);
+    assert_eq!(result, Ok(()));
+    assert_eq!(staked_count(&accounts), 0);
+}
+
+#[test]
+fn locked_receipts_cant_be_unstaked() {
+    let mut fixture = StakeFixture::new();
+    fixture.farm.min_lock_duration = 100;
+    fixture.receipt_count = Some(1);
+    let (result, _) = fixture.unstake_from_receipt(fixture.staked_data(NOW - 99));
+    assert_eq!(result, rejection(StakingError::InvalidWithdrawTime));
+    let (result, accounts) = fixture.unstake_from_receipt(fixture.staked_data(NOW - 100));
+    assert_eq!(result, Ok(()));
+    // The owner's reward account of the receipt unstake
+    assert_eq!(token_amount(&accounts[7]), 100);
+}
+
+#[test]
+fn farms_with_a_cooldown_require_an_unstake_request() {
+    let (result, _) = unstake(1_000, |farm| farm.cooldown_duration = 10);
+    assert_eq!(result, rejection(StakingError::CooldownRequired));
+    assert_eq!(finalize_unstake(0, 10).0, rejection(StakingError::UnstakeNotRequested));
+}
+
+#[test]
+fn unstake_is_finalized_once_the_cooldown_passed() {
+    // Requested 20 seconds after the stake, a cooldown of 80 seconds ends now
+    assert_eq!(finalize_unstake(NOW - 80, 81).0, rejection(StakingError::InvalidWithdrawTime));
+    let (result, accounts) = finalize_unstake(NOW - 80, 80);
+    assert_eq!(result, Ok(()));
+    assert_eq!(staked_count(&accounts), 0);
+    // Only the 20 seconds before the request accrued
+    assert_eq!(token_amount(&accounts[USER_REWARD]), 20);
+    assert_eq!(unstake_event(), (20, 0, 0));
+}
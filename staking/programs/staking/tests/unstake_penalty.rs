@@ -0,0 +1,55 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use common::{assert_error, farm};
+use staking::account::{FarmData, UserPool};
+use staking::constants::BPS_DENOMINATOR;
+use staking::error::StakingError;
+
+/// Farm paying 1 per second whose unstakes within 100 seconds forfeit half the reward
+fn penalty_farm() -> FarmData {
+    let mut farm = farm(0, 1);
+    farm.penalty_duration = 100;
+    farm.penalty_bps = 5_000;
+    farm
+}
+
+fn staked_pool(farm_pubkey: Pubkey, mint: Pubkey) -> UserPool {
+    let mut pool = UserPool::default();
+    pool.add_nft(mint, farm_pubkey, 0, BPS_DENOMINATOR, BPS_DENOMINATOR, 0).unwrap();
+    pool
+}
+
+#[test]
+fn claiming_before_unstaking_doesnt_avoid_the_penalty() {
+    let farm = penalty_farm();
+    let (farm_pubkey, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut pool = staked_pool(farm_pubkey, mint);
+
+    assert_error(pool.claim_reward(mint, farm_pubkey, &farm, 60), StakingError::ClaimInPenaltyPeriod);
+    assert_eq!(pool.claim_farm_reward(farm_pubkey, &farm, 60).unwrap(), 0);
+
+    // The unstake still owes the whole reward since the stake, half of it is forfeited
+    let (staked_data, reward) = pool.remove_nft(mint, farm_pubkey, &farm, 60).unwrap();
+    assert_eq!(reward, 60);
+    assert_eq!(farm.early_unstake_penalty(staked_data.staked_time, 60, reward).unwrap(), 30);
+}
+
+#[test]
+fn claims_resume_after_the_penalty_period() {
+    let farm = penalty_farm();
+    let (farm_pubkey, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut pool = staked_pool(farm_pubkey, mint);
+
+    assert_eq!(pool.claim_reward(mint, farm_pubkey, &farm, 100).unwrap(), 100);
+    assert_eq!(pool.claim_farm_reward(farm_pubkey, &farm, 150).unwrap(), 50);
+}
+
+#[test]
+fn farms_without_penalty_allow_claims() {
+    let farm = farm(0, 1);
+    let (farm_pubkey, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut pool = staked_pool(farm_pubkey, mint);
+
+    assert_eq!(pool.claim_reward(mint, farm_pubkey, &farm, 60).unwrap(), 60);
+}
@@ -0,0 +1,163 @@
+//! Stakes through `staking::entry` checking which whitelist entries allow an NFT and which rarity weights it can have.
+
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{entrypoint::ProgramResult, system_program};
+use anchor_lang::Discriminator;
+use common::harness::*;
+use common::rejection;
+use staking::account::{RarityProof, UserPool, WhitelistKind};
+use staking::constants::*;
+use staking::error::StakingError;
+
+/// Stakes into the fixture's farm, which has no merkle root, passing `entries` as the remaining accounts
+fn stake_with_entries(fixture: &StakeFixture, entries: Vec<TestAccount>) -> ProgramResult {
+    fixture.stake_to_pool(None, None, entries).0
+}
+
+/// Stakes passing `entries` after the mint's deny entry address, which isn't created
+fn stake(fixture: &StakeFixture, mut entries: Vec<TestAccount>) -> ProgramResult {
+    entries.insert(0, fixture.deny_entry(false));
+    stake_with_entries(fixture, entries)
+}
+
+#[test]
+fn any_verified_creator_matches_a_collection_entry() {
+    let (unverified, verified) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let fixture = StakeFixture::new().with_creators(&[(unverified, false), (verified, true)]);
+    assert_eq!(stake(&fixture, vec![fixture.entry(verified, WhitelistKind::Collection)]), Ok(()));
+    assert_eq!(
+        stake(&fixture, vec![fixture.entry(unverified, WhitelistKind::Collection)]),
+        rejection(StakingError::InvalidWhitelistAddress),
+    );
+}
+
+#[test]
+fn verified_creators_are_checked_next_to_a_verified_collection() {
+    let (collection, creator) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let fixture = StakeFixture::new()
+        .with_collection(collection, true)
+        .with_creators(&[(creator, true)]);
+    assert_eq!(stake(&fixture, vec![fixture.entry(collection, WhitelistKind::Collection)]), Ok(()));
+    assert_eq!(stake(&fixture, vec![fixture.entry(creator, WhitelistKind::Collection)]), Ok(()));
+}
+
+#[test]
+fn nfts_without_verified_collection_or_creator_are_rejected() {
+    let (collection, creator) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let fixture = StakeFixture::new()
+        .with_collection(collection, false)
+        .with_creators(&[(creator, false)]);
+    assert_eq!(
+        stake(&fixture, vec![fixture.entry(collection, WhitelistKind::Collection)]),
+        rejection(StakingError::UnverifiedCollection),
+    );
+}
+
+#[test]
+fn omitting_the_deny_entry_is_rejected() {
+    let fixture = StakeFixture::new();
+    let entry = || fixture.entry(fixture.nft_mint, WhitelistKind::Mint);
+    assert_eq!(stake_with_entries(&fixture, vec![entry()]), rejection(StakingError::MissingDenyAccount));
+    assert_eq!(stake_with_entries(&fixture, vec![entry(), fixture.deny_entry(false)]), Ok(()));
+
+    // The deny entry of another mint doesn't stand in for it
+    let mut other_mint = fixture.deny_entry(false);
+    other_mint.key = pda(&[Pubkey::new_unique().as_ref(), fixture.farm_pool.as_ref(), WhitelistKind::Deny.seed()]);
+    assert_eq!(stake_with_entries(&fixture, vec![entry(), other_mint]), rejection(StakingError::MissingDenyAccount));
+}
+
+#[test]
+fn created_deny_entry_rejects_wherever_it_is_passed() {
+    let fixture = StakeFixture::new();
+    for entries in [
+        vec![fixture.deny_entry(true), fixture.entry(fixture.nft_mint, WhitelistKind::Mint)],
+        vec![fixture.entry(fixture.nft_mint, WhitelistKind::Mint), fixture.deny_entry(true)],
+    ] {
+        assert_eq!(stake_with_entries(&fixture, entries), rejection(StakingError::DeniedNFT));
+    }
+}
+
+#[test]
+fn legacy_entries_match_their_stored_kind() {
+    let fixture = StakeFixture::new();
+    assert_eq!(stake(&fixture, vec![fixture.legacy_entry(fixture.nft_mint, WhitelistKind::Mint)]), Ok(()));
+
+    // A legacy collection entry of the mint's address doesn't allow it as a mint
+    assert_eq!(
+        stake(&fixture, vec![fixture.legacy_entry(fixture.nft_mint, WhitelistKind::Collection)]),
+        rejection(StakingError::UnverifiedCollection),
+    );
+}
+
+#[test]
+fn every_kind_has_its_own_seed() {
+    let kinds = [WhitelistKind::Mint, WhitelistKind::Collection, WhitelistKind::UpdateAuthority, WhitelistKind::Deny];
+    for (i, a) in kinds.iter().enumerate() {
+        assert!(!a.seed().is_empty());
+        for b in &kinds[i + 1..] {
+            assert_ne!(a.seed(), b.seed());
+        }
+    }
+}
+
+#[test]
+fn update_authority_entries_need_a_verified_creator() {
+    let (update_authority, creator) = (Pubkey::new_unique(), Pubkey::new_unique());
+    for (update_authority_verified, expected) in [
+        (false, rejection(StakingError::InvalidWhitelistAddress)),
+        (true, Ok(())),
+    ] {
+        let fixture = StakeFixture::new()
+            .with_update_authority(update_authority)
+            .with_creators(&[(creator, true), (update_authority, update_authority_verified)]);
+        let entry = fixture.entry(update_authority, WhitelistKind::UpdateAuthority);
+        assert_eq!(stake(&fixture, vec![entry]), expected);
+    }
+}
+
+#[test]
+fn entries_stored_before_the_multipliers_still_match() {
+    let fixture = StakeFixture::new();
+    let mut entry = fixture.legacy_entry(fixture.nft_mint, WhitelistKind::Mint);
+    // Discriminator, address and kind without the multiplier
+    entry.data.truncate(8 + 33);
+    assert_eq!(stake(&fixture, vec![entry]), Ok(()));
+}
+
+#[test]
+fn accounts_that_arent_entries_are_errors() {
+    let fixture = StakeFixture::new();
+
+    let mut wrong_owner = fixture.entry(fixture.nft_mint, WhitelistKind::Mint);
+    wrong_owner.owner = system_program::ID;
+    assert_eq!(
+        stake(&fixture, vec![wrong_owner]),
+        Err(ProgramError::Custom(ErrorCode::AccountOwnedByWrongProgram as u32)),
+    );
+
+    let mut bad_data = fixture.entry(fixture.nft_mint, WhitelistKind::Mint);
+    bad_data.data[..8].copy_from_slice(&UserPool::discriminator());
+    assert_eq!(
+        stake(&fixture, vec![bad_data]),
+        Err(ProgramError::Custom(ErrorCode::AccountDiscriminatorMismatch as u32)),
+    );
+}
+
+#[test]
+fn rarity_weights_are_bounded() {
+    let mut fixture = StakeFixture::new();
+    for (weight_bps, expected) in [
+        (MAX_RARITY_WEIGHT_BPS, Ok(())),
+        (MAX_RARITY_WEIGHT_BPS + 1, rejection(StakingError::InvalidRarityWeight)),
+        (0, rejection(StakingError::InvalidRarityWeight)),
+    ] {
+        // Table of the staked mint only, its proof is empty
+        fixture.farm.rarity_root = staking::merkle::rarity_leaf_hash(&fixture.nft_mint, weight_bps);
+        let entry = fixture.entry(fixture.nft_mint, WhitelistKind::Mint);
+        let rarity = RarityProof { weight_bps, proof: vec![] };
+        let entries = vec![fixture.deny_entry(false), entry];
+        assert_eq!(fixture.stake_to_pool(None, Some(rarity), entries).0, expected, "{}", weight_bps);
+    }
+}